@@ -1,4 +1,5 @@
 pub mod adam;
+mod compat;
 mod module;
 pub mod sdtm;
 pub mod tfls;
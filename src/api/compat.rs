@@ -0,0 +1,260 @@
+use super::module::{File, FileKind, FileStatus, Group, Item, Module, StatusReason, StatusRule};
+use crate::v2::{
+    inspect::{
+        evidence::{Evidence, Rule},
+        result::Status,
+    },
+    lifecycle, FileType, GroupLifecycle, InspectOption, InvestigatorParam, Kind, Lifecycle,
+    SequenceResult, Upstream,
+};
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use std::path::{Path, PathBuf};
+
+/// inspect with the v2 pipeline and shape the outcome as the module of v1
+pub fn inspect(config: &Path, root: &Path, kind: &Kind) -> Result<Module> {
+    let param = param(root);
+    // v1 never wrote a result cache into the study
+    let option = InspectOption {
+        cache: false,
+        ..Default::default()
+    };
+    let lifecycles = lifecycle(&param, config.to_path_buf(), kind, &option)?;
+    Ok(Module {
+        items: lifecycles
+            .iter()
+            .map(|lifecycle| item(lifecycle, kind))
+            .collect(),
+    })
+}
+
+/// v1 root is the purpose directory, which is `<root>\<product>\<trial>\stats\<purpose>` for v2,
+/// a directory laid out otherwise is taken as the purpose directory without naming the study
+fn param(root: &Path) -> InvestigatorParam<PathBuf> {
+    let names = root
+        .ancestors()
+        .take(4)
+        .map(|dir| {
+            dir.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .collect::<Option<Vec<_>>>()
+        .unwrap_or_default();
+    let parent = root.ancestors().nth(4);
+    match (names.as_slice(), parent) {
+        ([purpose, stats, trial, product], Some(parent)) if stats.eq_ignore_ascii_case("stats") => {
+            InvestigatorParam {
+                product: product.clone(),
+                trial: trial.clone(),
+                purpose: purpose.clone(),
+                root: parent.to_path_buf(),
+            }
+        }
+        _ => InvestigatorParam {
+            product: String::new(),
+            trial: String::new(),
+            purpose: String::new(),
+            root: root.to_path_buf(),
+        },
+    }
+}
+
+fn item(lifecycle: &Lifecycle, kind: &Kind) -> Item {
+    let name = match kind {
+        Kind::TFLs => lifecycle.item.clone(),
+        _ => lifecycle.item.to_uppercase(),
+    };
+    let upstream = lifecycle.upstream.as_ref();
+    // main qc result is audited ahead of the supp one
+    let qc = [
+        Some(&lifecycle.result.qc),
+        lifecycle.result.qc_supp.as_ref(),
+    ];
+    let production = group(&lifecycle.production, upstream, &[]);
+    let validation = group(&lifecycle.validation, upstream, &qc);
+    let mut timeline = upstream
+        .map(|upstream| File {
+            status: FileStatus::Fine,
+            name: upstream.name.clone(),
+            kind: FileKind::Input,
+            modified_at: timestamp(&upstream.modified_at),
+        })
+        .into_iter()
+        .collect::<Vec<_>>();
+    timeline.extend(
+        production
+            .files
+            .iter()
+            .chain(validation.files.iter())
+            .filter(|file| file.status.ne(&FileStatus::Missing))
+            .cloned(),
+    );
+    timeline.sort_by_key(|file| file.modified_at);
+    Item {
+        name,
+        timeline,
+        groups: vec![production, validation],
+    }
+}
+
+fn group(group: &GroupLifecycle, upstream: Option<&Upstream>, qc: &[Option<&Status>]) -> Group {
+    let reason = group
+        .evidence
+        .as_ref()
+        .filter(|_| group.status.explainable())
+        .and_then(|evidence| reason(evidence, &group.files, upstream));
+    let mut qc = qc.iter().flatten();
+    let files = group
+        .files
+        .iter()
+        .map(|sequence| {
+            let mut status = match sequence.status {
+                Status::Missing => FileStatus::Missing,
+                Status::Pass => FileStatus::Fine,
+                _ => FileStatus::Unexpected,
+            };
+            if sequence.kind.eq(&FileType::Qc) {
+                let matched = qc.next().map(|qc| !matches!(qc, Status::Failed(_)));
+                if status.eq(&FileStatus::Fine) && matched.eq(&Some(false)) {
+                    status = FileStatus::NotMatch;
+                }
+            }
+            File {
+                status,
+                name: stem(&sequence.name),
+                kind: file_kind(&sequence.kind),
+                modified_at: sequence.modified_at.as_ref().map(timestamp).unwrap_or(0),
+            }
+        })
+        .collect();
+    Group {
+        status: group.status,
+        reason,
+        files,
+    }
+}
+
+fn reason(
+    evidence: &Evidence,
+    files: &[SequenceResult],
+    upstream: Option<&Upstream>,
+) -> Option<StatusReason> {
+    let base_name = evidence.base.file_name()?.to_string_lossy();
+    let base = (base_name.as_ref(), timestamp(&evidence.base_time));
+    let compare_time = timestamp(evidence.compare_time.as_ref()?);
+    match evidence.rule {
+        Rule::UpstreamChanged => {
+            let upstream = upstream?;
+            let compare = (upstream.name.as_str(), compare_time);
+            Some(StatusReason::new(
+                StatusRule::UpstreamChanged,
+                base,
+                compare,
+            ))
+        }
+        Rule::SequenceOrder => {
+            let outdated_qc = files.iter().any(|file| {
+                file.kind.eq(&FileType::Qc) && file.evidence.as_ref().eq(&Some(evidence))
+            });
+            let rule = match outdated_qc {
+                true => StatusRule::QcOutdated,
+                false => StatusRule::OutOfOrder,
+            };
+            let compare_name = evidence.compare.as_ref()?.file_name()?.to_string_lossy();
+            let compare = (compare_name.as_ref(), compare_time);
+            Some(StatusReason::new(rule, base, compare))
+        }
+        Rule::CodeNotEdited | Rule::LogIssue | Rule::QcMismatch | Rule::SpecChanged => None,
+    }
+}
+
+fn file_kind(kind: &FileType) -> FileKind {
+    match kind {
+        FileType::Code => FileKind::SasCode,
+        FileType::Data => FileKind::SasData,
+        FileType::Xpt => FileKind::Xpt,
+        FileType::Output => FileKind::Output,
+        FileType::Log => FileKind::SasLog,
+        FileType::Qc => FileKind::QcResult,
+    }
+}
+
+/// file name without extension, as v1 names files
+fn stem(name: &str) -> String {
+    match Path::new(name).file_stem() {
+        Some(stem) => stem.to_string_lossy().to_string(),
+        None => name.into(),
+    }
+}
+
+fn timestamp(time: &DateTime<Local>) -> u64 {
+    time.timestamp().max(0) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::{Group as Side, GroupStatus, Investigator};
+    use chrono::TimeZone;
+
+    #[test]
+    fn param_test() {
+        let root = Path::new("studies")
+            .join("ak112")
+            .join("303")
+            .join("stats")
+            .join("CSR");
+        let param = param(&root);
+        assert_eq!(param.product, "ak112");
+        assert_eq!(param.trial, "303");
+        assert_eq!(param.purpose, "CSR");
+        assert_eq!(param.root, PathBuf::from("studies"));
+        // roots of other layouts, such as `<project>\inspector\CSR`, are used as they are
+        let other = Path::new("rtfs")
+            .join("202-113")
+            .join("inspector")
+            .join("CSR");
+        let param = super::param(&other);
+        assert!(param.product.is_empty() && param.trial.is_empty() && param.purpose.is_empty());
+        assert_eq!(Investigator::new(&param).root(), other);
+    }
+
+    #[test]
+    fn group_test() {
+        let time = Local.with_ymd_and_hms(2024, 3, 1, 9, 0, 0).unwrap();
+        let sequence = |name: &str, kind: FileType, status: Status| SequenceResult {
+            name: name.into(),
+            kind,
+            status,
+            group: Side::Validation,
+            modified_at: Some(time),
+            evidence: None,
+        };
+        let lifecycle = GroupLifecycle {
+            status: GroupStatus::NotMatch,
+            evidence: None,
+            files: vec![
+                sequence("v-ae.sas", FileType::Code, Status::Pass),
+                sequence("v-ae.rtf", FileType::Qc, Status::Pass),
+                sequence("v-suppae.rtf", FileType::Qc, Status::Pass),
+                sequence("v-ae.log", FileType::Log, Status::Missing),
+            ],
+        };
+        let failed = Status::Failed("Value differs".into());
+        let group = group(&lifecycle, None, &[Some(&Status::Pass), Some(&failed)]);
+        let statuses = group.files.iter().map(|f| f.status).collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                FileStatus::Fine,
+                FileStatus::Fine,
+                FileStatus::NotMatch,
+                FileStatus::Missing
+            ]
+        );
+        assert_eq!(group.files[0].name, "v-ae");
+        assert_eq!(group.files[0].kind, FileKind::SasCode);
+        assert_eq!(group.files[0].modified_at, time.timestamp() as u64);
+        assert!(group.reason.is_none());
+    }
+}
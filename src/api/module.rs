@@ -1,6 +1,7 @@
+use chrono::{Local, TimeZone};
 use serde::Serialize;
 
-use crate::module::{FileKind, FileStatus, GroupStatus};
+use crate::v2::GroupStatus;
 
 #[derive(Debug, Serialize)]
 pub struct Module {
@@ -17,6 +18,8 @@ pub struct Item {
 #[derive(Debug, Serialize)]
 pub struct Group {
     pub status: GroupStatus,
+    /// why status is `Unexpected` or `Changed`
+    pub reason: Option<StatusReason>,
     pub files: Vec<File>,
 }
 
@@ -27,3 +30,94 @@ pub struct File {
     pub kind: FileKind,
     pub modified_at: u64,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum FileKind {
+    SasData,
+    SasCode,
+    SasLog,
+    Output,
+    QcResult,
+    Xpt,
+    Input,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum FileStatus {
+    Fine,
+    Missing,
+    Unexpected,
+    NotMatch,
+}
+
+/// rule a group turned `Unexpected` or `Changed` by
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum StatusRule {
+    /// file modified before the latest upstream data, eg. raw data for sdtm
+    UpstreamChanged,
+    /// files are not modified in the expected order
+    OutOfOrder,
+    /// qc result modified before the datasets it compares
+    QcOutdated,
+}
+
+/// evidence of a status transition, base file is not modified later than compare file
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StatusReason {
+    pub rule: StatusRule,
+    pub base: String,
+    pub base_modified_at: u64,
+    pub compare: String,
+    pub compare_modified_at: u64,
+    pub message: String,
+}
+
+impl StatusReason {
+    pub fn new(rule: StatusRule, base: (&str, u64), compare: (&str, u64)) -> StatusReason {
+        let prefix = match rule {
+            StatusRule::UpstreamChanged => "Upstream data changed",
+            StatusRule::OutOfOrder => "Files modified out of order",
+            StatusRule::QcOutdated => "Qc result outdated",
+        };
+        let message = format!(
+            "{}: {} modified at {} is not later than {} modified at {}",
+            prefix,
+            base.0,
+            format_timestamp(base.1),
+            compare.0,
+            format_timestamp(compare.1)
+        );
+        StatusReason {
+            rule,
+            base: base.0.into(),
+            base_modified_at: base.1,
+            compare: compare.0.into(),
+            compare_modified_at: compare.1,
+            message,
+        }
+    }
+}
+
+fn format_timestamp(timestamp: u64) -> String {
+    match Local.timestamp_opt(timestamp as i64, 0).single() {
+        Some(time) => time.format("%Y-%m-%d %H:%M:%S").to_string(),
+        None => timestamp.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn status_reason_test() {
+        let reason = StatusReason::new(
+            StatusRule::QcOutdated,
+            ("v-ae.rtf", 1000),
+            ("ae.sas7bdat", 2000),
+        );
+        assert_eq!(reason.compare_modified_at, 2000);
+        assert!(reason
+            .message
+            .starts_with("Qc result outdated: v-ae.rtf modified at"));
+    }
+}
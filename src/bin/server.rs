@@ -0,0 +1,20 @@
+use anyhow::Result;
+use clap::Parser;
+use inspector::v2::serve;
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    serve(&args.addr, &args.root)?;
+    Ok(())
+}
+
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+struct Args {
+    /// address to listen on
+    #[arg(short, long, default_value = "127.0.0.1:8080")]
+    addr: String,
+    /// root directory of studies, eg. `D:\Studies`
+    #[arg(short, long)]
+    root: String,
+}
@@ -1,8 +1,4 @@
-mod adam;
 mod api;
-mod module;
-mod sdtm;
-mod tfls;
 mod utils;
 pub mod v2;
 
@@ -1,11 +0,0 @@
-mod file;
-mod group;
-mod item;
-mod module;
-mod paths;
-
-pub use file::{File, FileKind, FileStatus};
-pub use group::{Group, GroupKind, GroupStatus};
-pub use item::Item;
-pub use module::Module;
-pub use paths::Paths;
@@ -1,5 +0,0 @@
-mod inspect;
-mod spec;
-
-pub use inspect::Inspector;
-pub use spec::read_spec;
@@ -1,4 +0,0 @@
-mod inspect;
-mod top;
-pub use inspect::Inspector;
-pub use top::read_top;
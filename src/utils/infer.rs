@@ -3,6 +3,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use crate::v2::sort_specs;
 use anyhow::{anyhow, Ok, Result};
 use serde::{Deserialize, Serialize};
 
@@ -112,6 +113,8 @@ impl ProjectDirInfer {
                 targets.push(dir.join(name).as_os_str().to_string_lossy().to_string());
             }
         }
+        // the latest spec goes first, so it is picked without asking
+        sort_specs(&mut targets);
         Ok(targets)
     }
 }
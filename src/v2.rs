@@ -1,19 +1,61 @@
+mod bundle;
 mod category;
+mod compare;
 mod config;
+mod conformance;
 pub mod error;
 pub mod inspect;
 pub(crate) mod investigator;
+mod lifecycle;
+mod orphan;
+mod output_lint;
+mod output_review;
+mod p21;
 mod product;
 mod purpose;
+mod report;
+mod rtf;
 mod sequence;
+#[cfg(feature = "server")]
+mod server;
+mod spec;
+mod stage;
+mod study;
+mod summary;
+mod tracker;
 mod trial;
+mod watch;
+mod xpt;
 
-pub use category::{Group, Kind};
+pub use bundle::{verify_bundle, BundleResult};
+pub use category::{FileType, Group, Kind};
 pub use config::reader::read_config;
 pub use inspect::{
-    inspector::inspect, inspector::log_detail, inspector::qc_detail, inspector::sequence_detail,
-    result::InspectionResult,
+    cache::CacheStats, inspector::inspect, inspector::inspect_with, inspector::log_detail,
+    inspector::qc_detail, inspector::sequence_detail, inspector::InspectOption,
+    inspector::Inspection, result::InspectionResult, result::P21Counts, result::P21Result,
+    result::UnlistedSupp, timeline::TimelineEntry,
 };
-pub use investigator::investigator::{Investigator, InvestigatorParam};
+pub use investigator::{
+    investigator::{Investigator, InvestigatorParam},
+    raw::Upstream,
+};
+pub use lifecycle::{lifecycle, GroupLifecycle, GroupStatus, Lifecycle};
+pub use orphan::{orphan_detail, OrphanFile, OrphanGroup};
+pub use output_lint::UnlistedOutput;
+pub use p21::{attach_p21, read_p21_report, P21Issue, Severity};
 pub use product::{list_products, Product};
-pub use sequence::audit::AuditResult;
+pub use report::{render, report_section, save_report, ReportItem, ReportSection};
+pub use rtf::{read_text, RtfText};
+pub use sequence::audit::{AuditResult, SequenceResult};
+#[cfg(feature = "server")]
+pub use server::{handle, serve};
+pub use spec::{
+    attach_spec_changes, diff_spec, latest_spec, sort_specs, DomainChange, SpecDiff,
+    VariableChange, VariableChangeKind,
+};
+pub use stage::{stage, transitions, ItemStage, Stage, StageReport, Transition};
+pub use study::{sdtm_study, StudyDocument, StudySummary};
+pub use summary::{load_results, summarize, SummaryFormat, SummarySection};
+pub use tracker::{annotate_config, export_tracker};
+pub use watch::{watch, WatchMode};
@@ -0,0 +1,419 @@
+use super::{
+    category::{Group, Kind},
+    config::reader::read_config,
+    error::{Error, Result},
+    inspect::result::Status,
+    investigator::investigator::{Investigator, InvestigatorParam},
+    output_review::{output_number, title_number},
+    rtf,
+};
+use lopdf::{Dictionary, Document, Object, ObjectId};
+use serde::Serialize;
+use std::{
+    collections::HashSet,
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+/// combined output of all tfls compared with top, entries are identified by output number
+#[derive(Debug, Serialize)]
+pub struct BundleResult {
+    pub status: Status,
+    /// name of bundle file, none if not found
+    pub bundle: Option<String>,
+    /// output numbers in top but not in bundle
+    pub missing: Vec<String>,
+    /// output numbers found more than once in bundle
+    pub duplicated: Vec<String>,
+    /// output numbers in bundle but not in top
+    pub extra: Vec<String>,
+    /// output numbers placed against the order of top
+    #[serde(rename = "outOfOrder")]
+    pub out_of_order: Vec<String>,
+    /// items whose output is modified after bundle is assembled
+    pub outdated: Vec<String>,
+}
+
+impl From<Status> for BundleResult {
+    /// bundle could not be verified, eg. it is missing
+    fn from(status: Status) -> Self {
+        BundleResult {
+            status,
+            bundle: None,
+            missing: vec![],
+            duplicated: vec![],
+            extra: vec![],
+            out_of_order: vec![],
+            outdated: vec![],
+        }
+    }
+}
+
+/// verify the bundle lists every output of top once and in order, and it is newer than every output
+pub fn verify_bundle<P: AsRef<Path>>(
+    param: &InvestigatorParam<P>,
+    config_file: P,
+) -> Result<BundleResult> {
+    let config = read_config(config_file, &Kind::TFLs)?;
+    let investigator = Investigator::try_new(param)?;
+    let Some(bundle) = investigator.tfl_bundle() else {
+        return Ok(Status::Missing.into());
+    };
+    let pdf = bundle.format().as_deref() == Some("pdf");
+    let lines = match pdf {
+        true => fs::read(&bundle.filepath).and_then(|content| pdf_titles(&content)),
+        false => rtf::read_titles(&bundle.filepath),
+    }
+    .map_err(|source| Error::ReadOutputFailed {
+        name: "bundle".into(),
+        path: bundle.filepath.clone(),
+        source,
+    })?;
+    // titles of a pdf are only read from bookmarks, without them every output would be missing
+    if pdf && lines.is_empty() {
+        return Ok(BundleResult {
+            bundle: Some(bundle.name),
+            ..Status::Error("Bundle has no bookmarks".into()).into()
+        });
+    }
+    let expected = config
+        .iter()
+        .filter_map(|item| item.output.as_ref()?.number.as_deref())
+        .filter_map(output_number)
+        .collect::<Vec<_>>();
+    if expected.is_empty() {
+        return Ok(BundleResult {
+            bundle: Some(bundle.name),
+            ..Status::Error("No output number found in TOP".into()).into()
+        });
+    }
+    let mut result = compare(&expected, &entries(&lines));
+    result.bundle = Some(bundle.name.clone());
+    result.outdated = config
+        .iter()
+        .filter(|item| {
+            investigator
+                .tfl_output(&item.name, &Group::Production)
+                .is_some_and(|output| output.modified_at.gt(&bundle.modified_at))
+        })
+        .map(|item| item.name.clone())
+        .collect();
+    result.status = status(&result);
+    Ok(result)
+}
+
+/// output numbers of title lines, a title repeated on following pages is one entry
+fn entries(lines: &[String]) -> Vec<String> {
+    let mut entries: Vec<String> = vec![];
+    for number in lines.iter().filter_map(|line| title_number(line)) {
+        if entries.last().ne(&Some(&number)) {
+            entries.push(number);
+        }
+    }
+    entries
+}
+
+fn compare(expected: &[String], found: &[String]) -> BundleResult {
+    let mut result = BundleResult::from(Status::Pass);
+    for number in expected.iter() {
+        match found.iter().filter(|entry| entry.eq(&number)).count() {
+            0 => result.missing.push(number.clone()),
+            1 => {}
+            _ => result.duplicated.push(number.clone()),
+        }
+    }
+    for entry in found.iter() {
+        if !expected.contains(entry) && !result.extra.contains(entry) {
+            result.extra.push(entry.clone());
+        }
+    }
+    // first place of each output in bundle, by its index in top
+    let mut indexes: Vec<usize> = vec![];
+    for entry in found.iter() {
+        if let Some(index) = expected.iter().position(|number| number.eq(entry)) {
+            if !indexes.contains(&index) {
+                indexes.push(index);
+            }
+        }
+    }
+    let in_order = longest_increasing(&indexes);
+    result.out_of_order = indexes
+        .iter()
+        .filter(|index| !in_order.contains(index))
+        .map(|&index| expected[index].clone())
+        .collect();
+    result
+}
+
+/// the longest run of indexes kept in increasing order, others are the ones moved
+fn longest_increasing(indexes: &[usize]) -> Vec<usize> {
+    let mut lengths = vec![1; indexes.len()];
+    let mut previous = vec![None; indexes.len()];
+    for (i, index) in indexes.iter().enumerate() {
+        for (j, earlier) in indexes[..i].iter().enumerate() {
+            if earlier < index && lengths[j] + 1 > lengths[i] {
+                lengths[i] = lengths[j] + 1;
+                previous[i] = Some(j);
+            }
+        }
+    }
+    let mut run = vec![];
+    let mut current = (0..indexes.len()).max_by_key(|&i| (lengths[i], std::cmp::Reverse(i)));
+    while let Some(i) = current {
+        run.push(indexes[i]);
+        current = previous[i];
+    }
+    run
+}
+
+fn status(result: &BundleResult) -> Status {
+    let problems = [
+        (result.missing.len(), "missing"),
+        (result.duplicated.len(), "duplicated"),
+        (result.extra.len(), "extra"),
+        (result.out_of_order.len(), "out of order"),
+        (result.outdated.len(), "newer than bundle"),
+    ]
+    .into_iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, problem)| format!("{} {}", count, problem))
+    .collect::<Vec<_>>();
+    match problems.is_empty() {
+        true => Status::Pass,
+        false => Status::Failed(problems.join(", ")),
+    }
+}
+
+/// bookmark titles of pdf in outline order, a parent before its children, objects may be
+/// kept in compressed object streams, the title of document information is not a bookmark
+fn pdf_titles(content: &[u8]) -> io::Result<Vec<String>> {
+    let document =
+        Document::load_mem(content).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+    let Some(outlines) = document
+        .catalog()
+        .ok()
+        .and_then(|catalog| dictionary(&document, catalog.get(b"Outlines").ok()?))
+    else {
+        return Ok(vec![]);
+    };
+    let mut titles = vec![];
+    let mut visited = HashSet::new();
+    // next sibling is pushed before first child, so children are taken first
+    let mut pending = reference(outlines, b"First")
+        .into_iter()
+        .collect::<Vec<_>>();
+    while let Some(id) = pending.pop() {
+        // a broken outline may link back to an item already seen
+        if !visited.insert(id) {
+            continue;
+        }
+        let Some(item) = document
+            .get_object(id)
+            .ok()
+            .and_then(|item| dictionary(&document, item))
+        else {
+            continue;
+        };
+        pending.extend(reference(item, b"Next"));
+        pending.extend(reference(item, b"First"));
+        let title = item
+            .get(b"Title")
+            .ok()
+            .map(|title| resolve(&document, title));
+        if let Some(Object::String(bytes, _)) = title {
+            let title = pdf_string(bytes);
+            let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !title.is_empty() {
+                titles.push(title);
+            }
+        }
+    }
+    Ok(titles)
+}
+
+/// object an indirect reference points to, other objects as they are
+fn resolve<'a>(document: &'a Document, object: &'a Object) -> &'a Object {
+    match object {
+        Object::Reference(id) => document.get_object(*id).unwrap_or(object),
+        _ => object,
+    }
+}
+
+fn dictionary<'a>(document: &'a Document, object: &'a Object) -> Option<&'a Dictionary> {
+    resolve(document, object).as_dict().ok()
+}
+
+fn reference(dictionary: &Dictionary, key: &[u8]) -> Option<ObjectId> {
+    dictionary.get(key).and_then(Object::as_reference).ok()
+}
+
+/// text strings of pdf are utf-16 with byte order mark, or single byte otherwise
+fn pdf_string(bytes: &[u8]) -> String {
+    match bytes.strip_prefix(&[0xfe, 0xff]) {
+        Some(utf16) => {
+            let units = utf16
+                .chunks(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair.get(1).copied().unwrap_or_default()]))
+                .collect::<Vec<_>>();
+            String::from_utf16_lossy(&units)
+        }
+        None => match String::from_utf8(bytes.to_vec()) {
+            Ok(text) => text,
+            Err(_) => bytes.iter().map(|&byte| byte as char).collect(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::{dictionary, StringFormat};
+    use rust_xlsxwriter::Workbook;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_compare() {
+        let lines = strings(&[
+            "Protocol: AK112-303",
+            "Table 14.1.1 Summary of Demographics",
+            "Table 14.1.1 Summary of Demographics",
+            "Table 14.1.3 Exposure",
+            "Page 3",
+            "Listing 16.2.1 Discontinued Subjects",
+            "Table 14.1.2: Medical History",
+            "Table 14.1.3 Exposure",
+            "Figure 14.2.1 Kaplan-Meier Plot",
+        ]);
+        let found = entries(&lines);
+        assert_eq!(
+            found,
+            strings(&["14.1.1", "14.1.3", "16.2.1", "14.1.2", "14.1.3", "14.2.1"])
+        );
+        let expected = strings(&["14.1.1", "14.1.2", "14.1.3", "14.1.4", "16.2.1"]);
+        let result = compare(&expected, &found);
+        assert_eq!(result.missing, strings(&["14.1.4"]));
+        assert_eq!(result.duplicated, strings(&["14.1.3"]));
+        assert_eq!(result.extra, strings(&["14.2.1"]));
+        assert_eq!(result.out_of_order, strings(&["14.1.2"]));
+        assert_eq!(
+            status(&result),
+            Status::Failed("1 missing, 1 duplicated, 1 extra, 1 out of order".into())
+        );
+        let result = compare(&expected, &expected);
+        assert_eq!(status(&result), Status::Pass);
+    }
+
+    #[test]
+    fn test_pdf_titles() -> anyhow::Result<()> {
+        let mut document = Document::with_version("1.5");
+        let outlines = document.new_object_id();
+        let first = document.new_object_id();
+        let child = document.new_object_id();
+        let second = document.new_object_id();
+        document.objects.insert(
+            first,
+            dictionary! {
+                "Title" => Object::string_literal("Table 14.1.1 Summary of (All)  Subjects"),
+                "Parent" => outlines,
+                "First" => child,
+                "Last" => child,
+                "Next" => second,
+            }
+            .into(),
+        );
+        let utf16 = b"\xfe\xff\x00T\x00a\x00b\x00l\x00e\x00 \x001\x004\x00.\x001\x00.\x002";
+        document.objects.insert(
+            child,
+            dictionary! {
+                "Title" => Object::String(utf16.to_vec(), StringFormat::Hexadecimal),
+                "Parent" => first,
+            }
+            .into(),
+        );
+        // last item links back to the first one, which must not loop
+        document.objects.insert(
+            second,
+            dictionary! {
+                "Title" => Object::string_literal("Table 14.1.3 Exposure"),
+                "Parent" => outlines,
+                "Prev" => first,
+                "Next" => first,
+            }
+            .into(),
+        );
+        document.objects.insert(
+            outlines,
+            dictionary! { "Type" => "Outlines", "First" => first, "Last" => second }.into(),
+        );
+        let catalog =
+            document.add_object(dictionary! { "Type" => "Catalog", "Outlines" => outlines });
+        let info = document.add_object(dictionary! {
+            "Title" => Object::string_literal("Table 14.9.9 AK112-303 all outputs"),
+        });
+        document.trailer.set("Root", catalog);
+        document.trailer.set("Info", info);
+        let mut content = vec![];
+        document.save_to(&mut content)?;
+        let titles = pdf_titles(&content)?;
+        assert_eq!(
+            titles,
+            vec![
+                "Table 14.1.1 Summary of (All) Subjects",
+                "Table 14.1.2",
+                "Table 14.1.3 Exposure"
+            ]
+        );
+        assert_eq!(entries(&titles), strings(&["14.1.1", "14.1.2", "14.1.3"]));
+        assert!(pdf_titles(b"%PDF-1.4 not a document").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tfl_bundle() -> anyhow::Result<()> {
+        let root = std::env::temp_dir().join("inspector_bundle_test");
+        fs::remove_dir_all(&root).ok();
+        let param = InvestigatorParam {
+            product: "ak112".into(),
+            trial: "303".into(),
+            purpose: "CSR".into(),
+            root: root.as_path(),
+        };
+        let output = Investigator::new(&param)
+            .root()
+            .join(Group::Production.group_dir())
+            .join("output");
+        fs::create_dir_all(&output)?;
+        fs::write(output.join("t-14-2-1-combined-arms.rtf"), "")?;
+        fs::write(output.join("bundle-notes.txt"), "")?;
+        assert!(Investigator::new(&param).tfl_bundle().is_none());
+        fs::write(output.join("AK112-303 All TFLs.pdf"), "")?;
+        let bundle = Investigator::new(&param).tfl_bundle().unwrap();
+        assert_eq!(bundle.name, "AK112-303 All TFLs.pdf");
+
+        // a pdf saved without bookmarks gives no title to compare with top
+        let mut document = Document::with_version("1.5");
+        let catalog = document.add_object(dictionary! { "Type" => "Catalog" });
+        document.trailer.set("Root", catalog);
+        document.save(output.join("AK112-303 All TFLs.pdf"))?;
+        let top = root.join("top.xlsx");
+        let mut workbook = Workbook::new();
+        let sheet = workbook.add_worksheet().set_name("top")?;
+        sheet.write_string(0, 0, "Validation Level")?;
+        sheet.write_string(0, 4, "Output Name")?;
+        sheet.write_string(1, 4, "t-14-01-01")?;
+        workbook.save(&top)?;
+        let result = verify_bundle(&param, top.as_path())?;
+        assert_eq!(
+            result.status,
+            Status::Error("Bundle has no bookmarks".into())
+        );
+        assert_eq!(result.bundle.as_deref(), Some("AK112-303 All TFLs.pdf"));
+        assert!(result.missing.is_empty());
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+}
@@ -7,7 +7,7 @@ pub enum Kind {
     TFLs,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub enum Group {
     Production,
     Validation,
@@ -22,7 +22,7 @@ impl Group {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub enum FileType {
     Code,
     Data,
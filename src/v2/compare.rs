@@ -0,0 +1,171 @@
+use super::{
+    error::{Error, Result},
+    inspect::result::Status,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::{fs, path::Path};
+
+/// extentions of qc results written as proc compare listings instead of rtf
+pub(crate) const LISTING_FORMATS: [&str; 2] = ["lst", "txt"];
+
+lazy_static! {
+    static ref COMPARISON: Regex = Regex::new(r"(?i)^\s*Comparison of \S+ with \S+").unwrap();
+    static ref SUMMARY: Regex = Regex::new(r"(?i)^\s*(Number of .+?):\s*(\d+)").unwrap();
+    /// summary lines reporting a difference when their count is not zero
+    static ref DIFFERENCE: Regex = Regex::new(
+        r"(?i)but not in|Conflicting Types|Differing Attributes|Some Compared Variables Unequal|Some Observations Unequal"
+    )
+    .unwrap();
+    static ref UNEQUAL_VALUES: Regex =
+        Regex::new(r"(?i)^\s*Variables with Unequal Values\s*$").unwrap();
+    static ref UNEQUAL_VARIABLE: Regex = Regex::new(r"(?i)^\s*(\w+)\s+(?:NUM|CHAR)\s+\d+").unwrap();
+}
+
+/// whether qc result is a listing of proc compare, judged by its extention
+pub(crate) fn is_listing(path: &Path) -> bool {
+    path.extension()
+        .map(|extention| extention.to_string_lossy().to_lowercase())
+        .is_some_and(|extention| LISTING_FORMATS.contains(&extention.as_str()))
+}
+
+/// validate proc compare listing, variables in `ignore` are allowed to have unequal values
+pub(crate) fn validate(path: &Path, ignore: &[String]) -> Result<Status> {
+    let content = fs::read(path).map_err(|e| Error::qc(path, e))?;
+    Ok(review(&String::from_utf8_lossy(&content), ignore))
+}
+
+fn review(content: &str, ignore: &[String]) -> Status {
+    let mut comparisons = 0;
+    let mut differences = vec![];
+    let mut unequal = vec![];
+    let mut in_unequal_values = false;
+    for line in content.lines() {
+        if COMPARISON.is_match(line) {
+            comparisons += 1;
+            in_unequal_values = false;
+            continue;
+        }
+        if UNEQUAL_VALUES.is_match(line) {
+            in_unequal_values = true;
+            continue;
+        }
+        if in_unequal_values {
+            if let Some(captures) = UNEQUAL_VARIABLE.captures(line) {
+                let variable = captures[1].to_uppercase();
+                if !unequal.contains(&variable) {
+                    unequal.push(variable);
+                }
+            }
+            continue;
+        }
+        let Some(captures) = SUMMARY.captures(line) else {
+            continue;
+        };
+        let count = captures[2].parse::<usize>().unwrap_or_default();
+        if count > 0 && DIFFERENCE.is_match(&captures[1]) {
+            differences.push(format!("{}: {}", captures[1].trim(), count));
+        }
+    }
+    if comparisons == 0 {
+        return Status::Failed("No PROC COMPARE result found in listing".into());
+    }
+    // the table names variables behind the unequal counts, so only unignored ones are reported
+    if !unequal.is_empty() {
+        differences.retain(|difference| !difference.to_lowercase().contains(" unequal"));
+        let unequal = unequal
+            .into_iter()
+            .filter(|variable| !ignore.iter().any(|i| i.eq_ignore_ascii_case(variable)))
+            .collect::<Vec<_>>();
+        if !unequal.is_empty() {
+            differences.push(format!("Unequal values in {}", unequal.join(", ")));
+        }
+    }
+    match differences.is_empty() {
+        true => Status::Pass,
+        false => Status::Failed(differences.join("; ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EQUAL: &str = r"
+                                 The COMPARE Procedure
+                      Comparison of WORK.ADSL with QC.ADSL
+                                    (Method=EXACT)
+
+                 Number of Variables in Common: 48.
+                 Number of Observations in Common: 120.
+                 Number of Observations with Some Compared Variables Unequal: 0.
+                 Number of Observations with All Compared Variables Equal: 120.
+
+   NOTE: No unequal values were found. All values compared are exactly equal.
+";
+
+    const UNEQUAL: &str = r"
+                                 The COMPARE Procedure
+                      Comparison of WORK.ADSL with QC.ADSL
+                                    (Method=EXACT)
+
+                 Number of Variables in Common: 48.
+                 Number of Variables in WORK.ADSL but not in QC.ADSL: 1.
+                 Number of Observations with Some Compared Variables Unequal: 8.
+                 Number of Variables Compared with Some Observations Unequal: 2.
+
+                            Variables with Unequal Values
+
+                  Variable  Type  Len   Ndif   MaxDif
+
+                  AGE       NUM     8      1    1.000
+                  TRTSDTM   NUM     8      8    0.001
+";
+
+    const CONFLICTING: &str = r"
+                                 The COMPARE Procedure
+                      Comparison of WORK.ADSL with QC.ADSL
+                                    (Method=EXACT)
+
+                 Number of Variables in Common: 48.
+                 Number of Variables with Conflicting Types: 1.
+                 Number of Observations in Common: 120.
+                 Number of Observations with All Compared Variables Equal: 120.
+
+               Listing of Common Variables with Conflicting Types
+
+                  Variable  Dataset     Type  Length
+
+                  SITEID    WORK.ADSL   Num        8
+                            QC.ADSL     Char       3
+";
+
+    #[test]
+    fn test_review() {
+        assert!(is_listing(Path::new("v-adsl.LST")));
+        assert!(!is_listing(Path::new("v-adsl.rtf")));
+        assert_eq!(review(EQUAL, &[]), Status::Pass);
+        assert_eq!(
+            review(UNEQUAL, &[]),
+            Status::Failed(
+                "Number of Variables in WORK.ADSL but not in QC.ADSL: 1; Unequal values in AGE, TRTSDTM"
+                    .into()
+            )
+        );
+        assert_eq!(
+            review(UNEQUAL, &["trtsdtm".into()]),
+            Status::Failed(
+                "Number of Variables in WORK.ADSL but not in QC.ADSL: 1; Unequal values in AGE"
+                    .into()
+            )
+        );
+        assert_eq!(
+            review(CONFLICTING, &[]),
+            Status::Failed("Number of Variables with Conflicting Types: 1".into())
+        );
+        assert_eq!(
+            review("NOTE: The data set WORK.ADSL has 120 observations.", &[]),
+            Status::Failed("No PROC COMPARE result found in listing".into())
+        );
+    }
+}
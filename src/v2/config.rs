@@ -2,3 +2,5 @@ mod adam;
 pub mod reader;
 mod sdtm;
 mod tfl;
+pub mod title;
+pub mod variable;
@@ -1,9 +1,12 @@
-use super::reader::{Config, ConfigReader};
+use super::{
+    reader::{Config, ConfigReader},
+    variable::read_variables,
+};
 use crate::v2::error::Result;
 use calamine::{open_workbook, Data, DataType, Reader, Xlsx};
 use std::path::Path;
 
-const CONTENT: &str = "CONTENT";
+pub(crate) const CONTENT: &str = "CONTENT";
 const CONTENT_START_ROW: usize = 6;
 const DOMAIN_COLUMN: usize = 0;
 
@@ -37,6 +40,10 @@ impl ConfigReader for AdamConfigReader {
             }
             config.name = domain.clone();
             config.order = n;
+            // detail sheet is optional for adam spec, conformance check will be skipped without it
+            if let Ok(detail) = workbook.worksheet_range(&domain.to_uppercase()) {
+                config.variables = read_variables(&detail);
+            }
             configs.push(config);
         }
         Ok(configs)
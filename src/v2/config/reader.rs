@@ -1,17 +1,24 @@
-use super::{adam::AdamConfigReader, sdtm::SdtmConfigReader, tfl::TflConfigReader};
+use super::{
+    adam::AdamConfigReader, sdtm::SdtmConfigReader, tfl::TflConfigReader, title::OutputTitle,
+    variable::Variable,
+};
 use crate::v2::{category::Kind, error::Result};
+use serde::Serialize;
 use std::path::Path;
 
 pub trait ConfigReader {
     fn read(&self, file: &Path) -> Result<Vec<Config>>;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Config {
     pub name: String,
     pub supp: bool,
     pub qc: bool,
     pub order: usize,
+    pub variables: Vec<Variable>,
+    /// titles and footnotes of tfl output listed in top
+    pub output: Option<OutputTitle>,
 }
 
 impl Default for Config {
@@ -21,10 +28,21 @@ impl Default for Config {
             supp: false,
             qc: true,
             order: 0,
+            variables: vec![],
+            output: None,
         }
     }
 }
 
+/// sheet holding one row per item, `Config.order` is the row index in it
+pub(crate) fn content_sheet(kind: &Kind) -> &'static str {
+    match kind {
+        Kind::SDTM => super::sdtm::CONTENT,
+        Kind::ADaM => super::adam::CONTENT,
+        Kind::TFLs => super::tfl::TOP,
+    }
+}
+
 pub fn read_config<P: AsRef<Path>>(file: P, kind: &Kind) -> Result<Vec<Config>> {
     let reader: Box<dyn ConfigReader> = match kind {
         Kind::SDTM => Box::new(SdtmConfigReader::new()),
@@ -1,9 +1,12 @@
-use super::reader::{Config, ConfigReader};
+use super::{
+    reader::{Config, ConfigReader},
+    variable::read_variables,
+};
 use crate::v2::error::Result;
 use calamine::{open_workbook, Data, DataType, Range, Reader, Xlsx};
 use std::path::Path;
 
-const CONTENT: &str = "CONTENT";
+pub(crate) const CONTENT: &str = "CONTENT";
 const SUPP_PREFIX: &str = "SUPP";
 const DOMAIN_COLUMN: usize = 0;
 const CONTENT_START_ROW: usize = 6;
@@ -45,7 +48,10 @@ impl ConfigReader for SdtmConfigReader {
             }
             config.name = domain.clone();
             config.order = n;
-            config.supp = detect_supp_domain(&workbook.worksheet_range(&domain.to_uppercase())?);
+            let detail = workbook.worksheet_range(&domain.to_uppercase())?;
+            config.supp = detect_supp_domain(&detail);
+            // variables allocated to supp are kept, so moving them in or out of supp shows in spec diff
+            config.variables = read_variables(&detail);
             configs.push(config);
         }
         Ok(configs)
@@ -1,9 +1,12 @@
-use super::reader::{Config, ConfigReader};
+use super::{
+    reader::{Config, ConfigReader},
+    title::TitleColumns,
+};
 use crate::v2::error::Result;
 use calamine::{open_workbook, Data, DataType, Reader, Xlsx};
 use std::path::Path;
 
-const TOP: &str = "top";
+pub(crate) const TOP: &str = "top";
 const CONTENT_START_ROW: usize = 1;
 const OUTPUT_NAME_COLUMN: usize = 4;
 const VALIDATION_LEVEL_COLUMN: usize = 0;
@@ -24,9 +27,11 @@ impl ConfigReader for TflConfigReader {
         let mut workbook: Xlsx<_> = open_workbook(&file)?;
         let empty = Data::String("".into());
         let range = workbook.worksheet_range(TOP)?;
+        let mut title_columns = None;
         for (n, row) in range.rows().into_iter().enumerate() {
             // skipping untarget rows
             if n.lt(&CONTENT_START_ROW) {
+                title_columns = TitleColumns::detect(row);
                 continue;
             }
             let mut config = Config::default();
@@ -42,6 +47,7 @@ impl ConfigReader for TflConfigReader {
             config.name = domain.clone();
             config.order = n;
             config.qc = validation(row.get(VALIDATION_LEVEL_COLUMN));
+            config.output = title_columns.as_ref().map(|columns| columns.read(row));
             configs.push(config);
         }
         Ok(configs)
@@ -0,0 +1,81 @@
+use calamine::{Data, DataType};
+use serde::Serialize;
+
+// top files are written by different teams, so accept both chinese and english headers
+const NUMBER_HEADERS: [&str; 4] = ["编号", "Output Number", "Table Number", "Number"];
+const TITLE_HEADERS: [&str; 3] = ["标题", "Output Title", "Title"];
+const POPULATION_HEADERS: [&str; 3] = ["分析集", "Population", "Analysis Set"];
+const FOOTNOTE_HEADERS: [&str; 3] = ["脚注", "Footnotes", "Footnote"];
+
+/// titles and footnotes an output is expected to carry, as listed in top
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct OutputTitle {
+    /// table number, eg. `14.1.1.1`
+    pub number: Option<String>,
+    pub titles: Vec<String>,
+    pub population: Option<String>,
+    pub footnotes: Vec<String>,
+}
+
+impl OutputTitle {
+    pub fn is_empty(&self) -> bool {
+        self.number.is_none()
+            && self.titles.is_empty()
+            && self.population.is_none()
+            && self.footnotes.is_empty()
+    }
+}
+
+pub(crate) struct TitleColumns {
+    number: Option<usize>,
+    title: usize,
+    population: Option<usize>,
+    footnote: Option<usize>,
+}
+
+impl TitleColumns {
+    /// columns of header row, none if top has no title column
+    pub(crate) fn detect(row: &[Data]) -> Option<TitleColumns> {
+        let find = |headers: &[&str]| {
+            row.iter().position(|cell| {
+                let cell = cell.as_string().unwrap_or_default();
+                headers
+                    .iter()
+                    .any(|header| cell.trim().eq_ignore_ascii_case(header))
+            })
+        };
+        Some(TitleColumns {
+            number: find(&NUMBER_HEADERS),
+            title: find(&TITLE_HEADERS)?,
+            population: find(&POPULATION_HEADERS),
+            footnote: find(&FOOTNOTE_HEADERS),
+        })
+    }
+
+    /// titles and footnotes may take several lines of a cell
+    pub(crate) fn read(&self, row: &[Data]) -> OutputTitle {
+        let cell = |column: Option<usize>| {
+            column
+                .and_then(|column| row.get(column))
+                .and_then(|cell| cell.as_string())
+                .unwrap_or_default()
+        };
+        let text = |column: Option<usize>| {
+            let value = cell(column).trim().to_string();
+            (!value.is_empty()).then_some(value)
+        };
+        let lines = |column: Option<usize>| {
+            cell(column)
+                .lines()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect()
+        };
+        OutputTitle {
+            number: text(self.number),
+            titles: lines(Some(self.title)),
+            population: text(self.population),
+            footnotes: lines(self.footnote),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use calamine::{Data, DataType, Range};
+use serde::Serialize;
+
+// the detail sheets are written by different teams, so accept both chinese and english headers
+const NAME_HEADERS: [&str; 3] = ["变量名", "Variable Name", "Variable"];
+const LABEL_HEADERS: [&str; 3] = ["变量标签", "Variable Label", "Label"];
+const TYPE_HEADERS: [&str; 3] = ["类型", "Type", "Data Type"];
+const LENGTH_HEADERS: [&str; 2] = ["长度", "Length"];
+const ALLOCATION_HEADERS: [&str; 2] = ["变量归属", "Allocation"];
+// header row is expected in the first rows of detail sheet
+const HEADER_SEARCH_ROWS: usize = 20;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub enum VariableType {
+    Char,
+    Num,
+}
+
+impl VariableType {
+    /// map type in spec into sas storage type, eg. `text`, `datetime` => Char, `integer`, `float` => Num
+    pub fn from_spec(value: &str) -> Option<VariableType> {
+        let value = value.trim().to_lowercase();
+        if value.is_empty() {
+            return None;
+        }
+        match value.as_str() {
+            "num" | "numeric" | "integer" | "float" | "double" | "数值" | "数值型" => {
+                Some(VariableType::Num)
+            }
+            _ => Some(VariableType::Char),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match *self {
+            VariableType::Char => "Char",
+            VariableType::Num => "Num",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct Variable {
+    pub name: String,
+    pub label: String,
+    #[serde(rename = "dataType")]
+    pub data_type: Option<VariableType>,
+    pub length: Option<usize>,
+    pub allocation: String,
+}
+
+struct Columns {
+    name: usize,
+    label: Option<usize>,
+    data_type: Option<usize>,
+    length: Option<usize>,
+    allocation: Option<usize>,
+}
+
+/// read variables defined in domain detail sheet, stop at the first row without variable name
+pub(crate) fn read_variables(worksheet: &Range<Data>) -> Vec<Variable> {
+    let mut variables = vec![];
+    let mut header: Option<Columns> = None;
+    for (index, row) in worksheet.rows().enumerate() {
+        if header.is_none() {
+            if index.ge(&HEADER_SEARCH_ROWS) {
+                break;
+            }
+            header = detect_columns(row);
+            continue;
+        }
+        let columns = header.as_ref().unwrap();
+        let name = cell_string(row, Some(columns.name));
+        if name.is_empty() {
+            break;
+        }
+        variables.push(Variable {
+            name: name.to_uppercase(),
+            label: cell_string(row, columns.label),
+            data_type: VariableType::from_spec(&cell_string(row, columns.data_type)),
+            length: cell_string(row, columns.length).parse::<usize>().ok(),
+            allocation: cell_string(row, columns.allocation),
+        });
+    }
+    variables
+}
+
+fn detect_columns(row: &[Data]) -> Option<Columns> {
+    let find = |headers: &[&str]| {
+        row.iter().position(|cell| {
+            let cell = cell.as_string().unwrap_or_default();
+            headers.contains(&cell.trim())
+        })
+    };
+    Some(Columns {
+        name: find(&NAME_HEADERS)?,
+        label: find(&LABEL_HEADERS),
+        data_type: find(&TYPE_HEADERS),
+        length: find(&LENGTH_HEADERS),
+        allocation: find(&ALLOCATION_HEADERS),
+    })
+}
+
+fn cell_string(row: &[Data], column: Option<usize>) -> String {
+    column
+        .and_then(|column| row.get(column))
+        .and_then(|cell| cell.as_string())
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_read_variables() {
+        let mut range = Range::new((0, 0), (4, 4));
+        let header = [
+            "Variable Name",
+            "Variable Label",
+            "Type",
+            "Length",
+            "变量归属",
+        ];
+        for (col, value) in header.iter().enumerate() {
+            range.set_value((1, col as u32), Data::String(value.to_string()));
+        }
+        range.set_value((2, 0), Data::String("aeterm".into()));
+        range.set_value((2, 1), Data::String("Reported Term".into()));
+        range.set_value((2, 2), Data::String("text".into()));
+        range.set_value((2, 3), Data::Float(200f64));
+        range.set_value((2, 4), Data::String("AE".into()));
+        range.set_value((3, 0), Data::String("AESEQ".into()));
+        range.set_value((3, 2), Data::String("integer".into()));
+        let variables = read_variables(&range);
+        assert_eq!(variables.len(), 2);
+        assert_eq!(variables[0].name, "AETERM");
+        assert_eq!(variables[0].length, Some(200));
+        assert_eq!(variables[0].data_type, Some(VariableType::Char));
+        assert_eq!(variables[1].data_type, Some(VariableType::Num));
+        assert_eq!(variables[1].length, None);
+    }
+}
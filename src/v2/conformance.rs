@@ -0,0 +1,194 @@
+use super::{
+    config::{reader::Config, variable::Variable},
+    error::{Error, Result},
+    inspect::result::{ConformanceResult, Status, VariableAttribute, VariableMismatch},
+    investigator::investigator::File,
+    xpt::{self, DatasetVariable},
+};
+use std::{collections::HashMap, path::Path};
+
+const SUPP_ALLOCATION: &str = "SUPP";
+
+/// check the produced xpt against variables of spec, skip the check when spec contains no variable,
+/// a sas dataset not yet exported to xpt is not started rather than missing
+pub(crate) fn check<P: AsRef<Path>>(
+    config: &Config,
+    xpt: Option<File<P>>,
+    dataset: Option<File<P>>,
+) -> Result<Option<ConformanceResult>> {
+    // variables allocated to supp are stored as records of supp domain, not columns of main domain
+    let spec = config
+        .variables
+        .iter()
+        .filter(|variable| variable.allocation.ne(SUPP_ALLOCATION))
+        .cloned()
+        .collect::<Vec<_>>();
+    if spec.is_empty() {
+        return Ok(None);
+    }
+    match xpt {
+        Some(file) => {
+            let variables =
+                xpt::read_variables(&file.filepath).map_err(|source| Error::DatasetFailed {
+                    name: config.name.clone(),
+                    path: file.filepath.as_ref().to_path_buf(),
+                    source,
+                })?;
+            Ok(Some(conform(&spec, &variables)))
+        }
+        None if dataset.is_some() => Ok(Some(Status::NotStart.into())),
+        None => Ok(Some(Status::Missing.into())),
+    }
+}
+
+/// compare variables defined in spec with variables found in dataset
+pub(crate) fn conform(spec: &[Variable], data: &[DatasetVariable]) -> ConformanceResult {
+    let dataset = data
+        .iter()
+        .map(|variable| (variable.name.to_uppercase(), variable))
+        .collect::<HashMap<_, _>>();
+    let mut missing = vec![];
+    let mut mismatches = vec![];
+    for variable in spec.iter() {
+        let actual = match dataset.get(&variable.name.to_uppercase()) {
+            Some(actual) => actual,
+            None => {
+                missing.push(variable.name.clone());
+                continue;
+            }
+        };
+        if !variable.label.is_empty() && variable.label.ne(&actual.label) {
+            mismatches.push(VariableMismatch {
+                variable: variable.name.clone(),
+                attribute: VariableAttribute::Label,
+                spec: variable.label.clone(),
+                data: actual.label.clone(),
+            });
+        }
+        if let Some(data_type) = variable.data_type {
+            if data_type.ne(&actual.data_type) {
+                mismatches.push(VariableMismatch {
+                    variable: variable.name.clone(),
+                    attribute: VariableAttribute::Type,
+                    spec: data_type.as_str().into(),
+                    data: actual.data_type.as_str().into(),
+                });
+            }
+        }
+        if let Some(length) = variable.length {
+            if length.ne(&actual.length) {
+                mismatches.push(VariableMismatch {
+                    variable: variable.name.clone(),
+                    attribute: VariableAttribute::Length,
+                    spec: length.to_string(),
+                    data: actual.length.to_string(),
+                });
+            }
+        }
+    }
+    let extra = data
+        .iter()
+        .filter(|actual| {
+            !spec
+                .iter()
+                .any(|variable| variable.name.eq_ignore_ascii_case(&actual.name))
+        })
+        .map(|actual| actual.name.clone())
+        .collect::<Vec<_>>();
+    let status = if missing.is_empty() && extra.is_empty() && mismatches.is_empty() {
+        Status::Pass
+    } else {
+        Status::Failed(format!(
+            "{} missing, {} extra, {} mismatched",
+            missing.len(),
+            extra.len(),
+            mismatches.len()
+        ))
+    };
+    ConformanceResult {
+        status,
+        missing,
+        extra,
+        mismatches,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::config::variable::VariableType;
+    use chrono::Local;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_conform() {
+        let spec = vec![
+            Variable {
+                name: "AETERM".into(),
+                label: "Reported Term for the Adverse Event".into(),
+                data_type: Some(VariableType::Char),
+                length: Some(200),
+                allocation: "AE".into(),
+            },
+            Variable {
+                name: "AESEQ".into(),
+                label: "Sequence Number".into(),
+                data_type: Some(VariableType::Num),
+                length: None,
+                allocation: "AE".into(),
+            },
+        ];
+        let data = vec![
+            DatasetVariable {
+                name: "AETERM".into(),
+                label: "Reported Term".into(),
+                data_type: VariableType::Char,
+                length: 100,
+            },
+            DatasetVariable {
+                name: "AEDECOD".into(),
+                label: "Dictionary-Derived Term".into(),
+                data_type: VariableType::Char,
+                length: 200,
+            },
+        ];
+        let result = conform(&spec, &data);
+        assert_eq!(result.missing, vec!["AESEQ".to_string()]);
+        assert_eq!(result.extra, vec!["AEDECOD".to_string()]);
+        assert_eq!(result.mismatches.len(), 2);
+        assert_eq!(
+            result.status,
+            Status::Failed("1 missing, 1 extra, 2 mismatched".into())
+        );
+        assert_eq!(conform(&spec[..1], &data[..1]).mismatches.len(), 2);
+    }
+
+    #[test]
+    fn test_check_without_xpt() -> anyhow::Result<()> {
+        let config = Config {
+            name: "AE".into(),
+            variables: vec![Variable {
+                name: "AETERM".into(),
+                label: "Reported Term for the Adverse Event".into(),
+                data_type: Some(VariableType::Char),
+                length: Some(200),
+                allocation: "AE".into(),
+            }],
+            ..Default::default()
+        };
+        let dataset = File {
+            name: "ae.sas7bdat".into(),
+            filepath: PathBuf::from("ae.sas7bdat"),
+            created_at: Local::now(),
+            modified_at: Local::now(),
+        };
+        let status = |dataset| {
+            check(&config, None, dataset).map(|result| result.map(|result| result.status))
+        };
+        assert_eq!(status(Some(dataset))?, Some(Status::NotStart));
+        assert_eq!(status(None)?, Some(Status::Missing));
+        let config = Config::default();
+        assert!(check::<PathBuf>(&config, None, None)?.is_none());
+        Ok(())
+    }
+}
@@ -1,7 +1,13 @@
 use calamine::XlsxError;
-use std::io;
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 
+/// underlying error of a failure, kept as source so the whole chain can be reported
+pub type Cause = Box<dyn std::error::Error + Send + Sync>;
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Failed to list projects, because: {0}")]
@@ -16,11 +22,64 @@ pub enum Error {
     #[error("Failed to fetch metadata")]
     FetchMetadataFailed,
 
-    #[error("Failed in qc result validation")]
-    QcFailed,
+    #[error("Failed in qc result validation of {}, because: {source}", .path.display())]
+    QcFailed { path: PathBuf, source: Cause },
+
+    #[error("Failed in {item} log validation of {}, because: {source}", .path.display())]
+    LogFailed {
+        item: String,
+        path: PathBuf,
+        source: Cause,
+    },
+
+    #[error("Failed to read {name} dataset {}, because: {source}", .path.display())]
+    DatasetFailed {
+        name: String,
+        path: PathBuf,
+        source: io::Error,
+    },
+
+    #[error("Failed to read {name} output {}, because: {source}", .path.display())]
+    ReadOutputFailed {
+        name: String,
+        path: PathBuf,
+        source: io::Error,
+    },
+
+    #[error("Failed to watch directories, because: {0}")]
+    WatchFailed(#[from] notify::Error),
+
+    #[error("Failed to write result, because: {0}")]
+    OutputFailed(io::Error),
+
+    #[error("Failed to read result {}, because: {source}", .path.display())]
+    ReadResultFailed { path: PathBuf, source: Cause },
+
+    #[error("Failed to read P21 report {}, because: {source}", .path.display())]
+    ReadP21Failed { path: PathBuf, source: Cause },
+
+    #[error("Failed to read settings {}, because: {source}", .path.display())]
+    ReadSettingsFailed { path: PathBuf, source: Cause },
+
+    #[error("Failed to write workbook, because: {0}")]
+    WriteWorkbookFailed(#[from] rust_xlsxwriter::XlsxError),
+}
+
+impl Error {
+    pub(crate) fn qc<E: Into<Cause>>(path: &Path, source: E) -> Error {
+        Error::QcFailed {
+            path: path.to_path_buf(),
+            source: source.into(),
+        }
+    }
 
-    #[error("Failed in {0} log validation")]
-    LogFailed(String),
+    pub(crate) fn log<E: Into<Cause>>(item: &str, path: &Path, source: E) -> Error {
+        Error::LogFailed {
+            item: item.into(),
+            path: path.to_path_buf(),
+            source: source.into(),
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -1,2 +1,5 @@
+pub mod cache;
+pub mod evidence;
 pub mod inspector;
 pub mod result;
+pub mod timeline;
@@ -0,0 +1,195 @@
+use super::result::Status;
+use crate::v2::{error::Result, investigator::utils::system_time_to_chrono};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+pub const CACHE_FILE: &str = "inspector-cache.json";
+/// saves in this process, keeps temp files of concurrent saves apart
+static SAVES: AtomicUsize = AtomicUsize::new(0);
+
+/// outcomes of log and qc validation kept between runs, an outcome is reused
+/// while the size and modified time of the file are unchanged
+#[derive(Debug, Default)]
+pub struct ResultCache {
+    file: Option<PathBuf>,
+    entries: Mutex<CacheEntries>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheEntries {
+    log: HashMap<String, CacheEntry>,
+    qc: HashMap<String, CacheEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    size: u64,
+    #[serde(rename = "modifiedAt")]
+    modified_at: DateTime<Local>,
+    /// qc ignore patterns the outcome was produced with
+    ignore: Vec<String>,
+    status: Status,
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, PartialEq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl ResultCache {
+    /// load cache from file, start with an empty cache if missing or invalid
+    pub fn load<P: AsRef<Path>>(file: P) -> ResultCache {
+        let entries = fs::read_to_string(file.as_ref())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        ResultCache {
+            file: Some(file.as_ref().to_path_buf()),
+            entries: Mutex::new(entries),
+            ..Default::default()
+        }
+    }
+
+    /// cache which never reuses outcomes and is never saved
+    pub fn disabled() -> ResultCache {
+        ResultCache::default()
+    }
+
+    pub fn log<F>(&self, path: &Path, validate: F) -> Result<Status>
+    where
+        F: FnOnce() -> Result<Status>,
+    {
+        self.fetch(path, &[], |entries| &mut entries.log, validate)
+    }
+
+    pub fn qc<F>(&self, path: &Path, ignore: &[String], validate: F) -> Result<Status>
+    where
+        F: FnOnce() -> Result<Status>,
+    {
+        self.fetch(path, ignore, |entries| &mut entries.qc, validate)
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::SeqCst),
+            misses: self.misses.load(Ordering::SeqCst),
+        }
+    }
+
+    /// write cache back to file, nothing happens if cache is disabled; outcomes of files
+    /// removed since are dropped, and the file is replaced at once so that runs saving at
+    /// the same time never leave a partly written file
+    pub fn save(&self) -> io::Result<()> {
+        let Some(ref file) = self.file else {
+            return Ok(());
+        };
+        let content = {
+            let mut entries = self.entries.lock().unwrap();
+            entries.log.retain(|key, _| Path::new(key).exists());
+            entries.qc.retain(|key, _| Path::new(key).exists());
+            serde_json::to_string(&*entries)?
+        };
+        let temp = file.with_extension(format!(
+            "{}.{}.tmp",
+            std::process::id(),
+            SAVES.fetch_add(1, Ordering::SeqCst)
+        ));
+        fs::write(&temp, content)?;
+        fs::rename(&temp, file).inspect_err(|_| {
+            fs::remove_file(&temp).ok();
+        })
+    }
+
+    fn fetch<S, F>(&self, path: &Path, ignore: &[String], select: S, validate: F) -> Result<Status>
+    where
+        S: Fn(&mut CacheEntries) -> &mut HashMap<String, CacheEntry>,
+        F: FnOnce() -> Result<Status>,
+    {
+        let fingerprint = match (self.file.is_some(), fs::metadata(path)) {
+            (true, Ok(metadata)) => metadata
+                .modified()
+                .ok()
+                .map(|modified| (metadata.len(), system_time_to_chrono(&modified))),
+            _ => None,
+        };
+        let key = path.to_string_lossy().to_string();
+        if let Some((size, modified_at)) = fingerprint {
+            let mut entries = self.entries.lock().unwrap();
+            if let Some(entry) = select(&mut entries).get(&key) {
+                if entry.size.eq(&size)
+                    && entry.modified_at.eq(&modified_at)
+                    && entry.ignore.eq(ignore)
+                {
+                    self.hits.fetch_add(1, Ordering::SeqCst);
+                    return Ok(entry.status.clone());
+                }
+            }
+        }
+        self.misses.fetch_add(1, Ordering::SeqCst);
+        // errors are not cached, the file will be validated again next time
+        let status = validate()?;
+        if let Some((size, modified_at)) = fingerprint {
+            let mut entries = self.entries.lock().unwrap();
+            select(&mut entries).insert(
+                key,
+                CacheEntry {
+                    size,
+                    modified_at,
+                    ignore: ignore.to_vec(),
+                    status: status.clone(),
+                },
+            );
+        }
+        Ok(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_result_cache() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join("inspector_cache_test");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir)?;
+        let log = dir.join("ae.log");
+        fs::write(&log, "NOTE: DATA statement used")?;
+        let cache = ResultCache::load(dir.join(CACHE_FILE));
+        assert_eq!(cache.log(&log, || Ok(Status::Pass))?, Status::Pass);
+        assert_eq!(cache.log(&log, || Ok(Status::Missing))?, Status::Pass);
+        cache.save()?;
+        let cache = ResultCache::load(dir.join(CACHE_FILE));
+        assert_eq!(cache.log(&log, || Ok(Status::Missing))?, Status::Pass);
+        let removed = dir.join("dm.log");
+        fs::write(&removed, "")?;
+        cache.log(&removed, || Ok(Status::Pass))?;
+        fs::remove_file(&removed)?;
+        cache.save()?;
+        let saved = fs::read_to_string(dir.join(CACHE_FILE))?;
+        assert!(saved.contains("ae.log") && !saved.contains("dm.log"));
+        assert_eq!(fs::read_dir(&dir)?.count(), 2);
+        let ignore = vec!["WARNING".to_string()];
+        assert_eq!(
+            cache.qc(&log, &ignore, || Ok(Status::NotStart))?,
+            Status::NotStart
+        );
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 2 });
+        let disabled = ResultCache::disabled();
+        assert_eq!(disabled.log(&log, || Ok(Status::Pass))?, Status::Pass);
+        assert_eq!(disabled.log(&log, || Ok(Status::Missing))?, Status::Missing);
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
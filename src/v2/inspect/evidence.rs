@@ -0,0 +1,195 @@
+use super::result::Status;
+use crate::v2::investigator::{investigator::File, raw::Upstream};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// rule a failed verdict is based on
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum Rule {
+    /// compared file is modified later than the file depending on it
+    SequenceOrder,
+    /// code file is never modified after it was created
+    CodeNotEdited,
+    /// sas log contains issues
+    LogIssue,
+    /// qc result reports differences
+    QcMismatch,
+    /// file is modified before the latest upstream data
+    UpstreamChanged,
+    /// dataset is produced before the spec changing its domain
+    SpecChanged,
+}
+
+/// facts behind a failed verdict, so it can be explained without guesswork
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Evidence {
+    pub rule: Rule,
+    /// file the verdict is about
+    pub base: PathBuf,
+    /// modified time of base file
+    #[serde(rename = "baseTime")]
+    pub base_time: DateTime<Local>,
+    /// file the base file is compared with
+    pub compare: Option<PathBuf>,
+    /// modified time of compared file, created time for code never edited
+    #[serde(rename = "compareTime")]
+    pub compare_time: Option<DateTime<Local>>,
+    pub message: String,
+}
+
+impl Evidence {
+    /// base file is not modified after the compared file
+    pub fn sequence<P: AsRef<Path>>(base: &File<P>, compare: &File<P>, reason: &str) -> Evidence {
+        Evidence {
+            rule: Rule::SequenceOrder,
+            base: base.filepath.as_ref().to_path_buf(),
+            base_time: base.modified_at,
+            compare: Some(compare.filepath.as_ref().to_path_buf()),
+            compare_time: Some(compare.modified_at),
+            message: order_message(
+                reason,
+                (&compare.name, &compare.modified_at),
+                (&base.name, &base.modified_at),
+            ),
+        }
+    }
+
+    /// file named `base` is modified before the latest upstream data
+    pub fn upstream(base: &str, base_time: DateTime<Local>, upstream: &Upstream) -> Evidence {
+        Evidence {
+            rule: Rule::UpstreamChanged,
+            base: PathBuf::from(base),
+            base_time,
+            compare: Some(upstream.dir.clone()),
+            compare_time: Some(upstream.modified_at),
+            message: order_message(
+                "Upstream data changed",
+                (&upstream.name, &upstream.modified_at),
+                (base, &base_time),
+            ),
+        }
+    }
+
+    /// dataset is produced before the spec which changes its domain
+    pub fn spec<P: AsRef<Path>>(dataset: &File<P>, spec: &File<P>) -> Evidence {
+        Evidence {
+            rule: Rule::SpecChanged,
+            base: dataset.filepath.as_ref().to_path_buf(),
+            base_time: dataset.modified_at,
+            compare: Some(spec.filepath.as_ref().to_path_buf()),
+            compare_time: Some(spec.modified_at),
+            message: order_message(
+                "Spec changed after dataset produced",
+                (&spec.name, &spec.modified_at),
+                (&dataset.name, &dataset.modified_at),
+            ),
+        }
+    }
+
+    /// code file is still as it was created
+    pub fn code<P: AsRef<Path>>(code: &File<P>) -> Evidence {
+        Evidence {
+            rule: Rule::CodeNotEdited,
+            base: code.filepath.as_ref().to_path_buf(),
+            base_time: code.modified_at,
+            compare: Some(code.filepath.as_ref().to_path_buf()),
+            compare_time: Some(code.created_at),
+            message: format!(
+                "{} is not modified since it was created at {}",
+                code.name,
+                code.created_at.format(TIME_FORMAT)
+            ),
+        }
+    }
+
+    /// evidence of a failed log or qc check on file, none if the check did not fail
+    pub fn check<P: AsRef<Path>>(rule: Rule, file: &File<P>, status: &Status) -> Option<Evidence> {
+        let Status::Failed(reason) = status else {
+            return None;
+        };
+        let subject = match rule {
+            Rule::QcMismatch => "qc result",
+            _ => "log",
+        };
+        Some(Evidence {
+            rule,
+            base: file.filepath.as_ref().to_path_buf(),
+            base_time: file.modified_at,
+            compare: None,
+            compare_time: None,
+            message: format!(
+                "{} {} modified at {} failed: {}",
+                subject,
+                file.name,
+                file.modified_at.format(TIME_FORMAT),
+                reason
+            ),
+        })
+    }
+}
+
+fn order_message(
+    reason: &str,
+    later: (&str, &DateTime<Local>),
+    earlier: (&str, &DateTime<Local>),
+) -> String {
+    format!(
+        "{}: {} modified at {} is not earlier than {} modified at {}",
+        reason,
+        later.0,
+        later.1.format(TIME_FORMAT),
+        earlier.0,
+        earlier.1.format(TIME_FORMAT)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_evidence() {
+        let file = |name: &str, hour: u32| File {
+            name: name.into(),
+            filepath: PathBuf::from(name),
+            created_at: Local.with_ymd_and_hms(2024, 3, 1, 8, 0, 0).unwrap(),
+            modified_at: Local.with_ymd_and_hms(2024, 3, 1, hour, 0, 0).unwrap(),
+        };
+        let evidence = Evidence::sequence(
+            &file("ae.sas7bdat", 9),
+            &file("ae.sas", 10),
+            "Code later than data",
+        );
+        assert_eq!(evidence.rule, Rule::SequenceOrder);
+        assert_eq!(evidence.compare, Some(PathBuf::from("ae.sas")));
+        assert_eq!(
+            evidence.message,
+            "Code later than data: ae.sas modified at 2024-03-01 10:00:00 is not earlier than ae.sas7bdat modified at 2024-03-01 09:00:00"
+        );
+        let evidence = Evidence::code(&file("ae.sas", 8));
+        assert_eq!(evidence.compare_time, Some(evidence.base_time));
+        let log = file("ae.log", 11);
+        assert!(Evidence::check(Rule::LogIssue, &log, &Status::Pass).is_none());
+        let evidence = Evidence::check(Rule::LogIssue, &log, &Status::Failed("ERROR found".into()));
+        assert_eq!(
+            evidence.map(|e| e.message),
+            Some("log ae.log modified at 2024-03-01 11:00:00 failed: ERROR found".into())
+        );
+        let upstream = Upstream {
+            name: "raw data".into(),
+            dir: PathBuf::from("rawdata"),
+            modified_at: Local.with_ymd_and_hms(2024, 3, 2, 8, 0, 0).unwrap(),
+        };
+        let data = file("ae.sas7bdat", 9);
+        let evidence = Evidence::upstream(&data.name, data.modified_at, &upstream);
+        assert_eq!(evidence.rule, Rule::UpstreamChanged);
+        assert_eq!(
+            evidence.message,
+            "Upstream data changed: raw data modified at 2024-03-02 08:00:00 is not earlier than ae.sas7bdat modified at 2024-03-01 09:00:00"
+        );
+    }
+}
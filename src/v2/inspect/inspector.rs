@@ -1,16 +1,34 @@
-use super::result::{InspectionResult, LogResult, QcResult, Status};
+use super::{
+    cache::{CacheStats, ResultCache, CACHE_FILE},
+    evidence::{Evidence, Rule},
+    result::{InspectionResult, LogResult, QcResult, Status, UnlistedSupp},
+    timeline::{timeline, TimelineEntry},
+};
 use crate::v2::{
     category::{Group, Kind},
+    compare,
+    config::reader::Config,
     error::{Error, Result},
     investigator::investigator::{Investigator, InvestigatorParam},
+    output_lint::{lint_unlisted, UnlistedOutput},
+    p21::attach_p21,
     read_config,
     sequence::audit::{self, AuditResult},
+    spec::{attach_spec_changes, diff_spec},
 };
 use adam::AdamInspector;
-use sdtm::SdtmInspector;
-use std::path::Path;
+use sdtm::{unlisted_supps, SdtmInspector};
+use serde::Serialize;
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+};
 use tfl::TflInspector;
-use validator::{qc::QcResultValidator, sas_log::SasLogValidatior};
+use validator::{qc::QcResultValidator, result::ReportResult, sas_log::SasLogValidatior};
 
 mod adam;
 mod sdtm;
@@ -18,6 +36,51 @@ mod tfl;
 
 pub trait Inspector {
     fn inspect(&self) -> Result<Vec<InspectionResult>>;
+    fn inspect_item(&self, item: &Config) -> InspectionResult;
+    fn investigator(&self) -> &Investigator;
+}
+
+#[derive(Debug, Clone)]
+pub struct InspectOption {
+    pub qc_ignore: Vec<String>,
+    /// number of items inspected at the same time
+    pub workers: usize,
+    /// reuse log and qc outcomes of unchanged files from previous runs, off by default as it
+    /// writes `inspector-cache.json` under utility folder of study
+    pub cache: bool,
+    /// attach the timeline of artifacts to each result
+    pub timeline: bool,
+    /// attach issues of the latest pinnacle 21 report to sdtm and adam results
+    pub p21: bool,
+    /// spec of the previous version, items changed since then are checked against the spec
+    pub previous_spec: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Inspection {
+    pub results: Vec<InspectionResult>,
+    pub cache: CacheStats,
+    /// lint of rtf outputs top does not list, only for tfls
+    #[serde(rename = "unlistedOutputs")]
+    pub unlisted_outputs: Vec<UnlistedOutput>,
+    /// supp datasets whose parent domain spec does not list, only for sdtm
+    #[serde(rename = "unlistedSupps")]
+    pub unlisted_supps: Vec<UnlistedSupp>,
+}
+
+impl Default for InspectOption {
+    fn default() -> Self {
+        InspectOption {
+            qc_ignore: vec![],
+            workers: thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+            cache: false,
+            timeline: false,
+            p21: false,
+            previous_spec: None,
+        }
+    }
 }
 
 pub fn inspect<P: AsRef<Path>>(
@@ -26,14 +89,134 @@ pub fn inspect<P: AsRef<Path>>(
     kind: &Kind,
     qc_ignore: &[String],
 ) -> Result<Vec<InspectionResult>> {
-    let config = read_config(config_file, &kind)?;
-    let investigator = Investigator::new(param);
-    let inspector: Box<dyn Inspector> = match kind {
-        Kind::SDTM => Box::new(SdtmInspector::new(investigator, &config, qc_ignore)),
-        Kind::ADaM => Box::new(AdamInspector::new(investigator, &config, qc_ignore)),
-        Kind::TFLs => Box::new(TflInspector::new(investigator, &config, qc_ignore)),
+    let option = InspectOption {
+        qc_ignore: qc_ignore.to_vec(),
+        ..Default::default()
     };
-    inspector.inspect()
+    Ok(inspect_with(param, config_file, kind, &option)?.results)
+}
+
+pub fn inspect_with<P: AsRef<Path>>(
+    param: &InvestigatorParam<P>,
+    config_file: P,
+    kind: &Kind,
+    option: &InspectOption,
+) -> Result<Inspection> {
+    let config = read_config(config_file.as_ref(), &kind)?;
+    let investigator = Investigator::try_new(param)?;
+    let cache = cache(&investigator, option);
+    let inspector = inspector(kind, investigator, &config, option, cache.clone());
+    let mut results = inspector.inspect()?;
+    if option.p21 {
+        attach_p21(inspector.investigator(), kind, &mut results);
+    }
+    if let Some(previous) = option.previous_spec.as_ref() {
+        let spec = config_file.as_ref();
+        let diff = diff_spec(previous.as_path(), spec, kind)?;
+        attach_spec_changes(inspector.investigator(), kind, spec, &diff, &mut results);
+    }
+    let unlisted_outputs = match kind {
+        Kind::TFLs => lint_unlisted(inspector.investigator(), &config),
+        _ => vec![],
+    };
+    let unlisted_supps = match kind {
+        Kind::SDTM => unlisted_supps(inspector.investigator(), &config),
+        _ => vec![],
+    };
+    // cache only speeds up the next run, failing to save it does not fail the inspection
+    cache.save().ok();
+    Ok(Inspection {
+        results,
+        cache: cache.stats(),
+        unlisted_outputs,
+        unlisted_supps,
+    })
+}
+
+/// result cache in utility directory of study, a disabled one if option turns it off
+pub(crate) fn cache(investigator: &Investigator, option: &InspectOption) -> Arc<ResultCache> {
+    Arc::new(match option.cache {
+        true => ResultCache::load(investigator.utility().join(CACHE_FILE)),
+        false => ResultCache::disabled(),
+    })
+}
+
+pub(crate) fn inspector(
+    kind: &Kind,
+    investigator: Investigator,
+    config: &[Config],
+    option: &InspectOption,
+    cache: Arc<ResultCache>,
+) -> Box<dyn Inspector> {
+    match kind {
+        Kind::SDTM => Box::new(SdtmInspector::new(investigator, config, option, cache)),
+        Kind::ADaM => Box::new(AdamInspector::new(investigator, config, option, cache)),
+        Kind::TFLs => Box::new(TflInspector::new(investigator, config, option, cache)),
+    }
+}
+
+/// inspect items with at most `workers` threads, results keep the order of items
+fn parallel<T, R, F>(items: &[T], workers: usize, inspect: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(items.len()));
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let result = inspect(item);
+                results.lock().unwrap().push((index, result));
+            });
+        }
+    });
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// keep the error of one item as its status instead of aborting the whole inspection
+fn item_status(result: Result<Status>) -> Status {
+    result.unwrap_or_else(|e| Status::Error(e.to_string()))
+}
+
+/// same as `item_status`, evidence of a failed check is kept in `evidence`
+fn item_check(result: Result<(Status, Option<Evidence>)>, evidence: &mut Vec<Evidence>) -> Status {
+    match result {
+        Ok((status, found)) => {
+            evidence.extend(found);
+            status
+        }
+        Err(e) => item_status(Err(e)),
+    }
+}
+
+/// evidence of every failed file in sequence audits
+fn sequence_evidence(audits: &[AuditResult]) -> Vec<Evidence> {
+    audits
+        .iter()
+        .flat_map(|audit| audit.sequences.iter())
+        .filter_map(|sequence| sequence.evidence.clone())
+        .collect()
+}
+
+/// timeline of audited files and upstream data of kind
+fn sequence_timeline(
+    investigator: &Investigator,
+    kind: &Kind,
+    audits: &[AuditResult],
+) -> Vec<TimelineEntry> {
+    let upstream = investigator.upstream(kind);
+    timeline(
+        upstream.as_ref(),
+        audits.iter().flat_map(|audit| audit.sequences.iter()),
+    )
 }
 
 pub fn log_detail<P: AsRef<Path>>(
@@ -42,7 +225,17 @@ pub fn log_detail<P: AsRef<Path>>(
     kind: &Kind,
     group: &Group,
 ) -> Result<LogResult> {
-    let investigator = Investigator::new(param);
+    let investigator = Investigator::try_new(param)?;
+    item_log(&investigator, item, kind, group)
+}
+
+/// validate log of item with an investigator already built for the study
+pub(crate) fn item_log(
+    investigator: &Investigator,
+    item: &str,
+    kind: &Kind,
+    group: &Group,
+) -> Result<LogResult> {
     let validator = SasLogValidatior::new();
     let file = match kind {
         Kind::SDTM => investigator.sdtm_log(item, group),
@@ -53,12 +246,15 @@ pub fn log_detail<P: AsRef<Path>>(
         Some(file) => {
             let result = validator
                 .validate(&file.filepath)
-                .map_err(|_| Error::LogFailed(item.to_string()))?;
-            Ok(result.into())
+                .map_err(|e| Error::log(item, &file.filepath, e))?;
+            let mut result: LogResult = result.into();
+            result.evidence = Evidence::check(Rule::LogIssue, &file, &result.status);
+            Ok(result)
         }
         None => Ok(LogResult {
             status: Status::Missing,
             details: vec![],
+            evidence: None,
         }),
     }
 }
@@ -69,7 +265,7 @@ pub fn qc_detail<P: AsRef<Path>>(
     kind: &Kind,
     ignore: &[String],
 ) -> Result<Vec<QcResult>> {
-    let investigator = Investigator::new(param);
+    let investigator = Investigator::try_new(param)?;
     let mut items = Vec::with_capacity(2);
     match kind {
         Kind::SDTM => {
@@ -87,48 +283,147 @@ pub fn qc_detail<P: AsRef<Path>>(
         };
         match item {
             Some(file) => {
-                let mut validator =
-                    QcResultValidator::new(file.filepath, ignore).map_err(|_| Error::QcFailed)?;
-                let result = validator.validate().map_err(|_| Error::QcFailed)?;
-                match result {
-                    validator::result::ReportResult::Pass => results.push(QcResult {
-                        item_type,
-                        status: Status::Pass,
-                    }),
-                    validator::result::ReportResult::Unknown => results.push(QcResult {
-                        item_type,
-                        status: Status::Failed("Unknown Reason".into()),
-                    }),
-                    validator::result::ReportResult::Fail(reason) => results.push(QcResult {
-                        item_type,
-                        status: Status::Failed(reason),
-                    }),
-                }
+                let status = validate_qc(&file.filepath, ignore)?;
+                let evidence = Evidence::check(Rule::QcMismatch, &file, &status);
+                results.push(QcResult {
+                    item_type,
+                    status,
+                    evidence,
+                });
             }
             None => results.push(QcResult {
                 item_type,
                 status: Status::Missing,
+                evidence: None,
             }),
         }
     }
     Ok(results)
 }
 
+/// validate qc result, proc compare listings are read directly and other formats by the validator
+pub(crate) fn validate_qc(path: &Path, ignore: &[String]) -> Result<Status> {
+    if compare::is_listing(path) {
+        return compare::validate(path, ignore);
+    }
+    let mut validator =
+        QcResultValidator::new(path.to_path_buf(), ignore).map_err(|e| Error::qc(path, e))?;
+    let result = validator.validate().map_err(|e| Error::qc(path, e))?;
+    Ok(match result {
+        ReportResult::Pass => Status::Pass,
+        ReportResult::Unknown => Status::Failed("Unknown error".into()),
+        ReportResult::Fail(reason) => Status::Failed(reason),
+    })
+}
+
 pub fn sequence_detail<P: AsRef<Path>>(
     param: &InvestigatorParam<P>,
     item: &str,
     supp: bool,
     kind: &Kind,
+) -> Result<Vec<AuditResult>> {
+    let investigator = Investigator::try_new(param)?;
+    Ok(item_sequences(&investigator, item, supp, kind))
+}
+
+/// audit sequence of item with an investigator already built for the study
+pub(crate) fn item_sequences(
+    investigator: &Investigator,
+    item: &str,
+    supp: bool,
+    kind: &Kind,
 ) -> Vec<AuditResult> {
-    let investigator = Investigator::new(param);
-    let auditor = audit::new(item, kind, supp, &investigator);
+    let auditor = audit::new(item, kind, supp, investigator);
     auditor.audit()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::Path;
+    use std::{fs, path::Path};
+
+    #[test]
+    fn test_parallel_keep_order() {
+        let items = (0..100).collect::<Vec<usize>>();
+        let results = parallel(&items, 8, |item| item * 2);
+        assert_eq!(results, items.iter().map(|i| i * 2).collect::<Vec<_>>());
+        assert!(parallel(&Vec::<usize>::new(), 8, |item| *item).is_empty());
+    }
+
+    #[test]
+    fn test_item_status() {
+        let error = Error::qc(Path::new("v-t-14-01-01.rtf"), "invalid rtf");
+        assert_eq!(
+            item_status(Err(error)),
+            Status::Error(
+                "Failed in qc result validation of v-t-14-01-01.rtf, because: invalid rtf".into()
+            )
+        );
+        assert_eq!(item_status(Ok(Status::Pass)), Status::Pass);
+    }
+
+    #[test]
+    fn test_supp_check() -> anyhow::Result<()> {
+        let root = std::env::temp_dir().join("inspector_supp_check_test");
+        fs::remove_dir_all(&root).ok();
+        let param = InvestigatorParam {
+            product: "ak112".into(),
+            trial: "303".into(),
+            purpose: "CSR".into(),
+            root: root.as_path(),
+        };
+        let investigator = Investigator::new(&param);
+        let data = investigator.sdtm_directories()[1].path.clone();
+        fs::create_dir_all(&data)?;
+        for name in [
+            "supplb.sas7bdat",
+            "suppdm.xpt",
+            "suppmh.xpt",
+            "suppmh.sas7bdat",
+        ] {
+            fs::write(data.join(name), "")?;
+        }
+        let item = |name: &str, supp: bool| Config {
+            name: name.into(),
+            supp,
+            ..Default::default()
+        };
+        let config = vec![item("AE", true), item("DM", false), item("LB", true)];
+        let inspector = SdtmInspector::new(
+            investigator,
+            &config,
+            &InspectOption::default(),
+            Arc::new(ResultCache::disabled()),
+        );
+        let supp_check = |index: usize| inspector.inspect_item(&config[index]).supp_check;
+        assert_eq!(
+            supp_check(0),
+            Some(Status::Failed(
+                "SUPPAE allocated in spec but dataset not found".into()
+            ))
+        );
+        assert_eq!(
+            supp_check(1),
+            Some(Status::Failed(
+                "SUPPDM dataset found but not allocated in spec".into()
+            ))
+        );
+        assert_eq!(supp_check(2), Some(Status::Pass));
+        let unlisted = unlisted_supps(inspector.investigator(), &config);
+        assert_eq!(
+            unlisted
+                .iter()
+                .map(|supp| supp.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["suppmh.sas7bdat", "suppmh.xpt"]
+        );
+        assert_eq!(
+            unlisted[0].status,
+            Status::Failed("SUPPMH dataset found but domain MH not in spec".into())
+        );
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
 
     #[test]
     fn test_inspect_sdtm() -> anyhow::Result<()> {
@@ -1,74 +1,110 @@
-use super::{InspectionResult, Inspector};
+use super::{
+    item_check, item_status, parallel, sequence_evidence, sequence_timeline, validate_qc,
+    InspectOption, InspectionResult, Inspector,
+};
 use crate::v2::{
     category::Group,
     config::reader::Config,
+    conformance,
     error::{Error, Result},
-    inspect::result::{IndividualResult, Status},
+    inspect::{
+        cache::ResultCache,
+        evidence::{Evidence, Rule},
+        result::{ConformanceResult, IndividualResult, Status},
+        timeline::TimelineEntry,
+    },
     investigator::investigator::Investigator,
     sequence::audit,
     Kind,
 };
-use validator::{qc::QcResultValidator, result::ReportResult, sas_log::SasLogValidatior};
+use std::sync::Arc;
+use validator::{result::ReportResult, sas_log::SasLogValidatior};
 
 pub struct AdamInspector {
     investigator: Investigator,
     config: Vec<Config>,
     qc_ignore: Vec<String>,
+    workers: usize,
+    timeline: bool,
+    cache: Arc<ResultCache>,
 }
 
 impl AdamInspector {
     pub fn new(
         investigator: Investigator,
         config: &[Config],
-        qc_ignore: &[String],
+        option: &InspectOption,
+        cache: Arc<ResultCache>,
     ) -> AdamInspector {
         AdamInspector {
             investigator,
             config: config.to_vec(),
-            qc_ignore: qc_ignore.to_vec(),
+            qc_ignore: option.qc_ignore.clone(),
+            workers: option.workers,
+            timeline: option.timeline,
+            cache,
         }
     }
 
-    fn validate_log(&self, item: &str, group: &Group) -> Result<Status> {
+    fn validate_log(&self, item: &str, group: &Group) -> Result<(Status, Option<Evidence>)> {
         let target_file = self.investigator.adam_log(item, group);
         match target_file {
             Some(file) => {
-                let validator = SasLogValidatior::new();
-                let result = validator
-                    .validate(file.filepath)
-                    .map_err(|_| Error::LogFailed(item.to_string()))?;
-                match result.status {
-                    ReportResult::Pass => Ok(Status::Pass),
-                    ReportResult::Unknown => Ok(Status::Failed("Unknown error".into())),
-                    ReportResult::Fail(msg) => Ok(Status::Failed(msg)),
-                }
+                let status = self.cache.log(&file.filepath, || {
+                    let validator = SasLogValidatior::new();
+                    let result = validator
+                        .validate(&file.filepath)
+                        .map_err(|e| Error::log(item, &file.filepath, e))?;
+                    match result.status {
+                        ReportResult::Pass => Ok(Status::Pass),
+                        ReportResult::Unknown => Ok(Status::Failed("Unknown error".into())),
+                        ReportResult::Fail(msg) => Ok(Status::Failed(msg)),
+                    }
+                })?;
+                let evidence = Evidence::check(Rule::LogIssue, &file, &status);
+                Ok((status, evidence))
             }
-            None => Ok(Status::Missing),
+            None => Ok((Status::Missing, None)),
         }
     }
 
-    fn sequence(&self, item: &str) -> (Status, Status) {
+    fn sequence(
+        &self,
+        item: &str,
+        evidence: &mut Vec<Evidence>,
+    ) -> (Status, Status, Option<Vec<TimelineEntry>>) {
         let sequencer = audit::new(item, &Kind::ADaM, false, &self.investigator);
         let result = sequencer.audit();
-        (result[0].status.clone(), result[1].status.clone())
+        evidence.extend(sequence_evidence(&result));
+        let timeline = self
+            .timeline
+            .then(|| sequence_timeline(&self.investigator, &Kind::ADaM, &result));
+        (result[0].status.clone(), result[1].status.clone(), timeline)
     }
 
-    fn qc(&self, item: &str) -> Result<Status> {
+    fn qc(&self, item: &str) -> Result<(Status, Option<Evidence>)> {
         match self.investigator.adam_qc_result(item) {
             Some(file) => {
-                let mut qc = QcResultValidator::new(file.filepath, &self.qc_ignore)
-                    .map_err(|_| Error::QcFailed)?;
-                let result = qc.validate().map_err(|_| Error::QcFailed)?;
-                match result {
-                    ReportResult::Pass => Ok(Status::Pass),
-                    ReportResult::Unknown => Ok(Status::Failed("Unknown error".into())),
-                    ReportResult::Fail(msg) => Ok(Status::Failed(msg)),
-                }
+                let status = self.cache.qc(&file.filepath, &self.qc_ignore, || {
+                    validate_qc(&file.filepath, &self.qc_ignore)
+                })?;
+                let evidence = Evidence::check(Rule::QcMismatch, &file, &status);
+                Ok((status, evidence))
             }
-            None => Ok(Status::Missing),
+            None => Ok((Status::Missing, None)),
         }
     }
 
+    fn conformance(&self, config: &Config) -> Option<ConformanceResult> {
+        conformance::check(
+            config,
+            self.investigator.adam_xpt(&config.name),
+            self.investigator
+                .adam_data(&config.name, &Group::Production),
+        )
+        .unwrap_or_else(|e| Some(item_status(Err(e)).into()))
+    }
+
     fn start_coding(&self, item: &str, group: &Group) -> bool {
         let code_file = match group {
             Group::Production => self.investigator.adam_code(item, &Group::Production),
@@ -83,26 +119,46 @@ impl AdamInspector {
 
 impl Inspector for AdamInspector {
     fn inspect(&self) -> Result<Vec<InspectionResult>> {
-        let mut results = Vec::with_capacity(self.config.len());
-        for item in self.config.iter() {
-            let sequence = self.sequence(&item.name);
-            let result = InspectionResult {
-                item: item.name.clone(),
-                qc: self.qc(&item.name)?,
-                qc_supp: None,
-                production_result: IndividualResult {
-                    start_coding: self.start_coding(&item.name, &Group::Production),
-                    log: self.validate_log(&item.name, &Group::Production)?,
-                    sequence: sequence.0,
-                },
-                validation_result: IndividualResult {
-                    start_coding: self.start_coding(&item.name, &Group::Validation),
-                    log: self.validate_log(&item.name, &Group::Validation)?,
-                    sequence: sequence.1,
-                },
-            };
-            results.push(result);
+        Ok(parallel(&self.config, self.workers, |item| {
+            self.inspect_item(item)
+        }))
+    }
+
+    fn investigator(&self) -> &Investigator {
+        &self.investigator
+    }
+
+    fn inspect_item(&self, item: &Config) -> InspectionResult {
+        let mut evidence = vec![];
+        let qc = item_check(self.qc(&item.name), &mut evidence);
+        let production_log = self.validate_log(&item.name, &Group::Production);
+        let production_log = item_check(production_log, &mut evidence);
+        let validation_log = self.validate_log(&item.name, &Group::Validation);
+        let validation_log = item_check(validation_log, &mut evidence);
+        let sequence = self.sequence(&item.name, &mut evidence);
+        InspectionResult {
+            item: item.name.clone(),
+            qc,
+            production_result: IndividualResult {
+                start_coding: self.start_coding(&item.name, &Group::Production),
+                log: production_log,
+                sequence: sequence.0,
+            },
+            validation_result: IndividualResult {
+                start_coding: self.start_coding(&item.name, &Group::Validation),
+                log: validation_log,
+                sequence: sequence.1,
+            },
+            conformance: self.conformance(item),
+            fallbacks: self.investigator.fallbacks(&item.name),
+            case_conflicts: self.investigator.case_conflicts(&item.name),
+            evidence,
+            timeline: sequence.2,
+            qc_format: self
+                .investigator
+                .adam_qc_result(&item.name)
+                .and_then(|file| file.format()),
+            ..Default::default()
         }
-        Ok(results)
     }
 }
@@ -1,59 +1,88 @@
-use super::{InspectionResult, Inspector};
+use super::{
+    item_check, item_status, parallel, sequence_evidence, sequence_timeline, validate_qc,
+    InspectOption, InspectionResult, Inspector,
+};
 use crate::v2::{
     category::Group,
     config::reader::Config,
+    conformance,
     error::{Error, Result},
-    inspect::result::{IndividualResult, Status},
+    inspect::{
+        cache::ResultCache,
+        evidence::{Evidence, Rule},
+        result::{ConformanceResult, IndividualResult, Status, UnlistedSupp},
+        timeline::TimelineEntry,
+    },
     investigator::investigator::Investigator,
     sequence::audit,
     Kind,
 };
-use validator::{qc::QcResultValidator, result::ReportResult, sas_log::SasLogValidatior};
+use std::sync::Arc;
+use validator::{result::ReportResult, sas_log::SasLogValidatior};
 
 pub struct SdtmInspector {
     investigator: Investigator,
     config: Vec<Config>,
     qc_ignore: Vec<String>,
+    workers: usize,
+    timeline: bool,
+    cache: Arc<ResultCache>,
 }
 
 impl SdtmInspector {
     pub fn new(
         investigator: Investigator,
         config: &[Config],
-        qc_ignore: &[String],
+        option: &InspectOption,
+        cache: Arc<ResultCache>,
     ) -> SdtmInspector {
         SdtmInspector {
             investigator,
             config: config.to_vec(),
-            qc_ignore: qc_ignore.to_vec(),
+            qc_ignore: option.qc_ignore.clone(),
+            workers: option.workers,
+            timeline: option.timeline,
+            cache,
         }
     }
 
-    fn sequence(&self, config: &Config) -> (Status, Status) {
+    fn sequence(
+        &self,
+        config: &Config,
+        evidence: &mut Vec<Evidence>,
+    ) -> (Status, Status, Option<Vec<TimelineEntry>>) {
         let sequence = audit::new(&config.name, &Kind::SDTM, config.supp, &self.investigator);
         let result = sequence.audit();
-        (result[0].status.clone(), result[1].status.clone())
+        evidence.extend(sequence_evidence(&result));
+        let timeline = self
+            .timeline
+            .then(|| sequence_timeline(&self.investigator, &Kind::SDTM, &result));
+        (result[0].status.clone(), result[1].status.clone(), timeline)
     }
 
-    fn validate_log(&self, item: &str, group: &Group) -> Result<Status> {
+    fn validate_log(&self, item: &str, group: &Group) -> Result<(Status, Option<Evidence>)> {
         let target_file = self.investigator.sdtm_log(item, group);
         match target_file {
             Some(file) => {
-                let validator = SasLogValidatior::new();
-                let result = validator
-                    .validate(file.filepath)
-                    .map_err(|_| Error::LogFailed(item.to_string()))?;
-                match result.status {
-                    ReportResult::Pass => Ok(Status::Pass),
-                    ReportResult::Unknown => Ok(Status::Failed("Unknown error".into())),
-                    ReportResult::Fail(msg) => Ok(Status::Failed(msg)),
-                }
+                let status = self.cache.log(&file.filepath, || {
+                    let validator = SasLogValidatior::new();
+                    let result = validator
+                        .validate(&file.filepath)
+                        .map_err(|e| Error::log(item, &file.filepath, e))?;
+                    match result.status {
+                        ReportResult::Pass => Ok(Status::Pass),
+                        ReportResult::Unknown => Ok(Status::Failed("Unknown error".into())),
+                        ReportResult::Fail(msg) => Ok(Status::Failed(msg)),
+                    }
+                })?;
+                let evidence = Evidence::check(Rule::LogIssue, &file, &status);
+                Ok((status, evidence))
             }
-            None => Ok(Status::Missing),
+            None => Ok((Status::Missing, None)),
         }
     }
 
-    fn qc(&self, item: &str, supp: bool) -> Result<Status> {
+    fn qc(&self, item: &str, supp: bool) -> Result<(Status, Option<Evidence>)> {
         let target_file = if !supp {
             self.investigator.sdtm_qc_main(item)
         } else {
@@ -61,16 +90,13 @@ impl SdtmInspector {
         };
         match target_file {
             Some(file) => {
-                let mut qc = QcResultValidator::new(file.filepath, &self.qc_ignore)
-                    .map_err(|_| Error::QcFailed)?;
-                let result = qc.validate().map_err(|_| Error::QcFailed)?;
-                match result {
-                    ReportResult::Pass => Ok(Status::Pass),
-                    ReportResult::Unknown => Ok(Status::Failed("Unknown error".into())),
-                    ReportResult::Fail(msg) => Ok(Status::Failed(msg)),
-                }
+                let status = self.cache.qc(&file.filepath, &self.qc_ignore, || {
+                    validate_qc(&file.filepath, &self.qc_ignore)
+                })?;
+                let evidence = Evidence::check(Rule::QcMismatch, &file, &status);
+                Ok((status, evidence))
             }
-            None => Ok(Status::Missing),
+            None => Ok((Status::Missing, None)),
         }
     }
     fn start_coding(&self, item: &str, group: Group) -> bool {
@@ -84,40 +110,119 @@ impl SdtmInspector {
         }
     }
 
-    fn qc_main(&self, item: &str) -> Result<Status> {
+    fn conformance(&self, config: &Config) -> Option<ConformanceResult> {
+        conformance::check(
+            config,
+            self.investigator.sdtm_xpt_main(&config.name),
+            self.investigator.sdtm_data_main_production(&config.name),
+        )
+        .unwrap_or_else(|e| Some(item_status(Err(e)).into()))
+    }
+
+    /// reconcile supp domain allocated in spec with supp datasets actually produced
+    fn supp_check(&self, config: &Config) -> Status {
+        let produced = self
+            .investigator
+            .sdtm_data_supp_production(&config.name)
+            .is_some()
+            || self.investigator.sdtm_xpt_supp(&config.name).is_some();
+        let domain = format!("SUPP{}", config.name.to_uppercase());
+        match (config.supp, produced) {
+            (true, false) => Status::Failed(format!(
+                "{} allocated in spec but dataset not found",
+                domain
+            )),
+            (false, true) => Status::Failed(format!(
+                "{} dataset found but not allocated in spec",
+                domain
+            )),
+            _ => Status::Pass,
+        }
+    }
+
+    fn qc_main(&self, item: &str) -> Result<(Status, Option<Evidence>)> {
         self.qc(item, false)
     }
 
-    fn qc_supp(&self, item: &str) -> Result<Status> {
+    fn qc_supp(&self, item: &str) -> Result<(Status, Option<Evidence>)> {
         self.qc(item, true)
     }
 }
 
 impl Inspector for SdtmInspector {
     fn inspect(&self) -> Result<Vec<InspectionResult>> {
-        let mut results = Vec::with_capacity(self.config.len());
-        for item in self.config.iter() {
-            let sequence = self.sequence(item);
-            let mut result = InspectionResult {
-                item: item.name.clone(),
-                qc: self.qc_main(&item.name)?,
-                qc_supp: None,
-                production_result: IndividualResult {
-                    start_coding: self.start_coding(&item.name, Group::Production),
-                    log: self.validate_log(&item.name, &Group::Production)?,
-                    sequence: sequence.0,
-                },
-                validation_result: IndividualResult {
-                    start_coding: self.start_coding(&item.name, Group::Validation),
-                    log: self.validate_log(&item.name, &Group::Validation)?,
-                    sequence: sequence.1,
-                },
-            };
-            if item.supp {
-                result.qc_supp = Some(self.qc_supp(&item.name)?);
-            }
-            results.push(result);
+        Ok(parallel(&self.config, self.workers, |item| {
+            self.inspect_item(item)
+        }))
+    }
+
+    fn investigator(&self) -> &Investigator {
+        &self.investigator
+    }
+
+    fn inspect_item(&self, item: &Config) -> InspectionResult {
+        let mut evidence = vec![];
+        let qc = item_check(self.qc_main(&item.name), &mut evidence);
+        let production_log = self.validate_log(&item.name, &Group::Production);
+        let production_log = item_check(production_log, &mut evidence);
+        let validation_log = self.validate_log(&item.name, &Group::Validation);
+        let validation_log = item_check(validation_log, &mut evidence);
+        let sequence = self.sequence(item, &mut evidence);
+        let mut result = InspectionResult {
+            item: item.name.clone(),
+            qc,
+            production_result: IndividualResult {
+                start_coding: self.start_coding(&item.name, Group::Production),
+                log: production_log,
+                sequence: sequence.0,
+            },
+            validation_result: IndividualResult {
+                start_coding: self.start_coding(&item.name, Group::Validation),
+                log: validation_log,
+                sequence: sequence.1,
+            },
+            conformance: self.conformance(item),
+            supp_check: Some(self.supp_check(item)),
+            timeline: sequence.2,
+            qc_format: self
+                .investigator
+                .sdtm_qc_main(&item.name)
+                .and_then(|file| file.format()),
+            ..Default::default()
+        };
+        if item.supp {
+            result.qc_supp = Some(item_check(self.qc_supp(&item.name), &mut evidence));
         }
-        Ok(results)
+        result.evidence = evidence;
+        result.fallbacks = self.investigator.fallbacks(&item.name);
+        result.case_conflicts = self.investigator.case_conflicts(&item.name);
+        result
     }
 }
+
+/// supp datasets and transport files in production data directory whose parent domain
+/// spec does not list, eg. `suppae.xpt` without `AE` in spec
+pub(crate) fn unlisted_supps(investigator: &Investigator, config: &[Config]) -> Vec<UnlistedSupp> {
+    investigator
+        .sdtm_supp_files()
+        .into_iter()
+        .filter_map(|file| {
+            let stem = file.filepath.file_stem()?.to_string_lossy().to_uppercase();
+            let domain = stem.strip_prefix("SUPP")?.to_string();
+            if config
+                .iter()
+                .any(|item| item.name.eq_ignore_ascii_case(&domain))
+            {
+                return None;
+            }
+            Some(UnlistedSupp {
+                status: Status::Failed(format!(
+                    "{} dataset found but domain {} not in spec",
+                    stem, domain
+                )),
+                name: file.name,
+                path: file.filepath,
+            })
+        })
+        .collect()
+}
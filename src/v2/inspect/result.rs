@@ -1,7 +1,11 @@
-use serde::Serialize;
+use super::{evidence::Evidence, timeline::TimelineEntry};
+use crate::v2::investigator::investigator::{CaseConflict, FallbackMatch};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use validator::result::ReportResult;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct InspectionResult {
     pub item: String,
     pub qc: Status,
@@ -11,9 +15,106 @@ pub struct InspectionResult {
     pub production_result: IndividualResult,
     #[serde(rename = "validationResult")]
     pub validation_result: IndividualResult,
+    pub conformance: Option<ConformanceResult>,
+    #[serde(rename = "suppCheck")]
+    pub supp_check: Option<Status>,
+    /// files located by normalized name or alias instead of the exact name
+    #[serde(default)]
+    pub fallbacks: Vec<FallbackMatch>,
+    /// files of item only differ in case in the same directory
+    #[serde(rename = "caseConflicts", default)]
+    pub case_conflicts: Vec<CaseConflict>,
+    /// facts behind failed log, qc and sequence checks
+    #[serde(default)]
+    pub evidence: Vec<Evidence>,
+    /// artifacts of item in the order they are modified, only built when asked for
+    #[serde(default)]
+    pub timeline: Option<Vec<TimelineEntry>>,
+    /// issues found by pinnacle 21, only attached when asked for
+    #[serde(default)]
+    pub p21: Option<P21Result>,
+    /// whether dataset is produced after the spec changed its domain, only for changed domains
+    #[serde(rename = "specChange", default)]
+    pub spec_change: Option<Status>,
+    /// titles and footnotes of tfl output compared with top
+    #[serde(rename = "outputReview", default)]
+    pub output_review: Option<OutputReviewResult>,
+    /// structural findings of tfl output, eg. placeholders left in rtf
+    #[serde(rename = "outputLint", default)]
+    pub output_lint: Option<OutputLintResult>,
+    /// extention of the output found, only for tfl
+    #[serde(rename = "outputFormat", default)]
+    pub output_format: Option<String>,
+    /// extention of the qc result found, eg. `lst` for proc compare listings
+    #[serde(rename = "qcFormat", default)]
+    pub qc_format: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+impl InspectionResult {
+    /// status of every check of item with its name, checks not applicable are skipped
+    pub fn checks(&self) -> Vec<(&'static str, &Status)> {
+        let checks = [
+            ("QC", Some(&self.qc)),
+            ("QC Supp", self.qc_supp.as_ref()),
+            ("Production Log", Some(&self.production_result.log)),
+            (
+                "Production Sequence",
+                Some(&self.production_result.sequence),
+            ),
+            ("Validation Log", Some(&self.validation_result.log)),
+            (
+                "Validation Sequence",
+                Some(&self.validation_result.sequence),
+            ),
+            ("Supp Check", self.supp_check.as_ref()),
+            ("Conformance", self.conformance.as_ref().map(|c| &c.status)),
+            ("P21", self.p21.as_ref().map(|p21| &p21.status)),
+            ("Spec Change", self.spec_change.as_ref()),
+            (
+                "Output Review",
+                self.output_review.as_ref().map(|review| &review.status),
+            ),
+            (
+                "Output Lint",
+                self.output_lint.as_ref().map(|lint| &lint.status),
+            ),
+        ];
+        checks
+            .into_iter()
+            .filter_map(|(name, status)| status.map(|status| (name, status)))
+            .collect()
+    }
+
+    /// the worst status among checks
+    pub fn overall(&self) -> &Status {
+        self.checks()
+            .into_iter()
+            .map(|(_, status)| status)
+            .min_by_key(|status| status.severity())
+            .unwrap_or(&self.qc)
+    }
+}
+
+/// issues of item in pinnacle 21 report by severity
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct P21Counts {
+    pub reject: usize,
+    pub error: usize,
+    pub warning: usize,
+    pub notice: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct P21Result {
+    pub report: PathBuf,
+    #[serde(rename = "reportTime")]
+    pub report_time: DateTime<Local>,
+    pub counts: P21Counts,
+    /// failed if report is older than xpt files of item, missing without xpt files
+    pub status: Status,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct IndividualResult {
     #[serde(rename = "startCoding")]
     pub start_coding: bool,
@@ -21,12 +122,16 @@ pub struct IndividualResult {
     pub sequence: Status,
 }
 
-#[derive(Debug, Serialize, PartialEq, Clone)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 pub enum Status {
     Pass,
     Failed(String),
     Missing,
+    /// nothing is checked yet, the default of results built field by field
+    #[default]
     NotStart,
+    /// the check itself failed, eg. file could not be read
+    Error(String),
 }
 
 impl Status {
@@ -36,12 +141,42 @@ impl Status {
             _ => false,
         }
     }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Pass => "Pass",
+            Status::Failed(_) => "Failed",
+            Status::Missing => "Missing",
+            Status::NotStart => "Not Start",
+            Status::Error(_) => "Error",
+        }
+    }
+
+    /// rank used to find the worst status, lower is worse
+    pub fn severity(&self) -> u8 {
+        match self {
+            Status::Error(_) => 0,
+            Status::Failed(_) => 1,
+            Status::Missing => 2,
+            Status::NotStart => 3,
+            Status::Pass => 4,
+        }
+    }
+
+    /// reason of failure or error
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Status::Failed(reason) | Status::Error(reason) => Some(reason),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
 pub struct LogResult {
     pub status: Status,
     pub details: Vec<LogRow>,
+    pub evidence: Option<Evidence>,
 }
 
 #[derive(Debug, Serialize)]
@@ -56,6 +191,120 @@ pub struct QcResult {
     #[serde(rename = "itemType")]
     pub item_type: String,
     pub status: Status,
+    pub evidence: Option<Evidence>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConformanceResult {
+    pub status: Status,
+    pub missing: Vec<String>,
+    pub extra: Vec<String>,
+    pub mismatches: Vec<VariableMismatch>,
+}
+
+impl From<Status> for ConformanceResult {
+    /// conformance could not be checked, eg. dataset missing or unreadable
+    fn from(status: Status) -> Self {
+        ConformanceResult {
+            status,
+            missing: vec![],
+            extra: vec![],
+            mismatches: vec![],
+        }
+    }
+}
+
+/// supp dataset whose parent domain is not an item of spec, so no item reports it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnlistedSupp {
+    pub name: String,
+    pub path: PathBuf,
+    pub status: Status,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VariableMismatch {
+    pub variable: String,
+    pub attribute: VariableAttribute,
+    pub spec: String,
+    pub data: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OutputReviewResult {
+    pub status: Status,
+    pub mismatches: Vec<TitleMismatch>,
+}
+
+impl From<Status> for OutputReviewResult {
+    /// output could not be reviewed, eg. rtf missing or unreadable
+    fn from(status: Status) -> Self {
+        OutputReviewResult {
+            status,
+            mismatches: vec![],
+        }
+    }
+}
+
+/// line of top not found in output, with what output has instead if it can be told
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct TitleMismatch {
+    pub field: TitleField,
+    pub top: String,
+    pub output: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum TitleField {
+    Number,
+    Title,
+    Population,
+    Footnote,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OutputLintResult {
+    pub status: Status,
+    pub pages: usize,
+    pub findings: Vec<LintFinding>,
+}
+
+impl From<Status> for OutputLintResult {
+    /// output could not be linted, eg. rtf missing or unreadable
+    fn from(status: Status) -> Self {
+        OutputLintResult {
+            status,
+            pages: 0,
+            findings: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct LintFinding {
+    pub rule: LintRule,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum LintRule {
+    /// body reports no data or holds no text
+    NoData,
+    /// text left for values or macro variables never resolved, or sas errors
+    Placeholder,
+    /// header and footer name different programs, or not the program of item
+    ProgramMismatch,
+    /// output is dated before the data cut
+    OutdatedOutput,
+    /// output is in production output directory but no item of top is saved as it
+    NotInTop,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum VariableAttribute {
+    Label,
+    Length,
+    Type,
 }
 
 impl From<validator::sas_log::LogResult> for LogResult {
@@ -68,6 +317,7 @@ impl From<validator::sas_log::LogResult> for LogResult {
         LogResult {
             status,
             details: value.details.into_iter().map(|row| row.into()).collect(),
+            evidence: None,
         }
     }
 }
@@ -0,0 +1,107 @@
+use super::evidence::Rule;
+use crate::v2::{
+    category::{FileType, Group},
+    investigator::raw::Upstream,
+    sequence::audit::SequenceResult,
+};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+/// one artifact of item placed on its timeline
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TimelineEntry {
+    pub name: String,
+    /// none for upstream data
+    pub kind: Option<FileType>,
+    pub group: Option<Group>,
+    #[serde(rename = "modifiedAt")]
+    pub modified_at: DateTime<Local>,
+    /// modified before a file it depends on, or before the latest upstream data
+    #[serde(rename = "outOfOrder")]
+    pub out_of_order: bool,
+}
+
+/// upstream data and every existing file audited, earliest first
+pub fn timeline<'a, I>(upstream: Option<&Upstream>, files: I) -> Vec<TimelineEntry>
+where
+    I: IntoIterator<Item = &'a SequenceResult>,
+{
+    let mut timeline = upstream
+        .map(|upstream| TimelineEntry {
+            name: upstream.name.clone(),
+            kind: None,
+            group: None,
+            modified_at: upstream.modified_at,
+            out_of_order: false,
+        })
+        .into_iter()
+        .collect::<Vec<_>>();
+    for file in files {
+        let Some(modified_at) = file.modified_at else {
+            continue;
+        };
+        let unordered = file
+            .evidence
+            .as_ref()
+            .is_some_and(|evidence| evidence.rule.eq(&Rule::SequenceOrder));
+        let stale = upstream.is_some_and(|upstream| {
+            file.kind.ne(&FileType::Code) && modified_at.lt(&upstream.modified_at)
+        });
+        timeline.push(TimelineEntry {
+            name: file.name.clone(),
+            kind: Some(file.kind),
+            group: Some(file.group),
+            modified_at,
+            out_of_order: unordered || stale,
+        });
+    }
+    // stable sort keeps upstream ahead of files modified at the same time
+    timeline.sort_by_key(|entry| entry.modified_at);
+    timeline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::inspect::{evidence::Evidence, result::Status};
+    use chrono::TimeZone;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_timeline() {
+        let time = |hour: u32| Local.with_ymd_and_hms(2024, 3, 1, hour, 0, 0).unwrap();
+        let file = |name: &str, kind: FileType, hour: Option<u32>| SequenceResult {
+            name: name.into(),
+            kind,
+            status: Status::Pass,
+            group: Group::Production,
+            modified_at: hour.map(time),
+            evidence: None,
+        };
+        let mut data = file("ae.sas7bdat", FileType::Data, Some(9));
+        data.evidence = Some(Evidence {
+            rule: Rule::SequenceOrder,
+            base: PathBuf::from("ae.sas7bdat"),
+            base_time: time(9),
+            compare: Some(PathBuf::from("ae.sas")),
+            compare_time: Some(time(11)),
+            message: "Code later than data".into(),
+        });
+        let files = vec![
+            file("ae.sas", FileType::Code, Some(11)),
+            data,
+            file("ae.xpt", FileType::Xpt, Some(6)),
+            file("ae.log", FileType::Log, None),
+        ];
+        let upstream = Upstream {
+            name: "raw data".into(),
+            dir: PathBuf::from("rawdata"),
+            modified_at: time(7),
+        };
+        let timeline = timeline(Some(&upstream), &files);
+        let names = timeline.iter().map(|e| e.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["ae.xpt", "raw data", "ae.sas7bdat", "ae.sas"]);
+        let flags = timeline.iter().map(|e| e.out_of_order).collect::<Vec<_>>();
+        assert_eq!(flags, vec![true, false, true, false]);
+    }
+}
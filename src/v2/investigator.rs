@@ -1,6 +1,9 @@
 mod adam;
 pub(crate) mod investigator;
-mod raw;
+pub(crate) mod raw;
+mod review;
 mod sdtm;
+pub(crate) mod settings;
+pub(crate) mod snapshot;
 mod tfl;
 pub(crate) mod utils;
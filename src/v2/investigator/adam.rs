@@ -1,57 +1,80 @@
-use super::{
-    investigator::{File, Investigator},
-    utils::{file, filename},
-};
+use super::investigator::{Directory, File, Investigator};
 use crate::v2::category::{FileType, Group};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-const CODE_DIR: &str = r"program\adam";
-const DATA_DIR: &str = r"dataset\adam";
-const QC_DIR: &str = r"qc-result\adam";
+fn code_dir() -> PathBuf {
+    Path::new("program").join("adam")
+}
+
+pub(super) fn data_dir() -> PathBuf {
+    Path::new("dataset").join("adam")
+}
+
+fn qc_dir() -> PathBuf {
+    Path::new("qc-result").join("adam")
+}
 
 impl Investigator {
+    pub fn adam_directories(&self) -> Vec<Directory> {
+        let production = self.root().join(Group::Production.group_dir());
+        let validation = self.root().join(Group::Validation.group_dir());
+        vec![
+            Directory {
+                path: production.join(code_dir()),
+                group: Group::Production,
+                file_types: vec![FileType::Code, FileType::Log],
+            },
+            Directory {
+                path: production.join(data_dir()),
+                group: Group::Production,
+                file_types: vec![FileType::Data, FileType::Xpt],
+            },
+            Directory {
+                path: validation.join(code_dir()),
+                group: Group::Validation,
+                file_types: vec![FileType::Code, FileType::Log],
+            },
+            Directory {
+                path: validation.join(data_dir()),
+                group: Group::Validation,
+                file_types: vec![FileType::Data],
+            },
+            Directory {
+                path: validation.join(qc_dir()),
+                group: Group::Validation,
+                file_types: vec![FileType::Qc],
+            },
+        ]
+    }
+
     pub fn adam_code(&self, item: &str, group: &Group) -> Option<File<PathBuf>> {
-        let filepath = self
-            .root()
-            .join(group.group_dir())
-            .join(CODE_DIR)
-            .join(filename(item, group, &FileType::Code));
-        file(filepath)
+        let dir = self.root().join(group.group_dir()).join(code_dir());
+        self.locate(dir, item, group, &FileType::Code)
     }
 
     pub fn adam_data(&self, item: &str, group: &Group) -> Option<File<PathBuf>> {
-        let filepath = self
-            .root()
-            .join(group.group_dir())
-            .join(DATA_DIR)
-            .join(filename(item, group, &FileType::Data));
-        file(filepath)
+        let dir = self.root().join(group.group_dir()).join(data_dir());
+        self.locate(dir, item, group, &FileType::Data)
     }
 
     pub fn adam_xpt(&self, item: &str) -> Option<File<PathBuf>> {
-        let filepath = self
+        let dir = self
             .root()
             .join(Group::Production.group_dir())
-            .join(DATA_DIR)
-            .join(filename(item, &Group::Production, &FileType::Xpt));
-        file(filepath)
+            .join(data_dir());
+        self.locate(dir, item, &Group::Production, &FileType::Xpt)
     }
 
     pub fn adam_log(&self, item: &str, group: &Group) -> Option<File<PathBuf>> {
-        let filepath = self
-            .root()
-            .join(group.group_dir())
-            .join(CODE_DIR)
-            .join(filename(item, group, &FileType::Log));
-        file(filepath)
+        let dir = self.root().join(group.group_dir()).join(code_dir());
+        self.locate(dir, item, group, &FileType::Log)
     }
 
     pub fn adam_qc_result(&self, item: &str) -> Option<File<PathBuf>> {
-        let filepath = self
+        let dir = self
             .root()
             .join(Group::Validation.group_dir())
-            .join(QC_DIR)
-            .join(filename(item, &Group::Validation, &FileType::Qc));
-        file(filepath)
+            .join(qc_dir());
+        self.locate(dir, item, &Group::Validation, &FileType::Qc)
     }
 }
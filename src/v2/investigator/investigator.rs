@@ -1,11 +1,30 @@
+use super::{
+    settings::{Settings, SETTINGS_FILE},
+    snapshot::Snapshot,
+    utils::{filename_as, normalize},
+};
+use crate::v2::{
+    category::{FileType, Group},
+    error::Result,
+    Kind,
+};
 use chrono::{DateTime, Local};
-use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
 
 pub struct Investigator {
     product: String,
     trial: String,
     purpose: String,
     root: PathBuf,
+    settings: Settings,
+    fallbacks: Mutex<Vec<FallbackMatch>>,
+    conflicts: Mutex<Vec<CaseConflict>>,
+    snapshots: Mutex<HashMap<PathBuf, Arc<Snapshot>>>,
 }
 
 pub struct InvestigatorParam<P: AsRef<Path>> {
@@ -15,22 +34,242 @@ pub struct InvestigatorParam<P: AsRef<Path>> {
     pub root: P,
 }
 
+/// file found by fallback matching instead of the exact name derived from config
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FallbackMatch {
+    pub item: String,
+    pub expected: String,
+    pub actual: String,
+    pub rule: MatchRule,
+}
+
+/// several files in one directory only differ in case, which breaks users on windows
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CaseConflict {
+    pub item: String,
+    pub directory: String,
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum MatchRule {
+    /// same name after ignoring case and treating `_` as `-`
+    Normalized,
+    /// name from alias table in study settings
+    Alias,
+}
+
 impl Investigator {
+    /// investigator with settings of study, default settings are used if the settings file
+    /// is malformed, see `try_new` to fail on it
     pub fn new<P: AsRef<Path>>(param: &InvestigatorParam<P>) -> Investigator {
+        let mut investigator = Investigator::bare(param);
+        if let Ok(settings) = Settings::read(investigator.utility().join(SETTINGS_FILE)) {
+            investigator.settings = settings;
+        }
+        investigator
+    }
+
+    /// investigator with settings of study, a malformed settings file is an error
+    pub fn try_new<P: AsRef<Path>>(param: &InvestigatorParam<P>) -> Result<Investigator> {
+        let mut investigator = Investigator::bare(param);
+        investigator.settings = Settings::read(investigator.utility().join(SETTINGS_FILE))?;
+        Ok(investigator)
+    }
+
+    fn bare<P: AsRef<Path>>(param: &InvestigatorParam<P>) -> Investigator {
         Investigator {
             product: param.product.clone(),
             trial: param.trial.clone(),
             purpose: param.purpose.clone(),
             root: param.root.as_ref().to_path_buf(),
+            settings: Settings::default(),
+            fallbacks: Mutex::new(vec![]),
+            conflicts: Mutex::new(vec![]),
+            snapshots: Mutex::new(HashMap::new()),
         }
     }
+
+    /// purpose directory of study, `root` itself when no study is named, for directories
+    /// laid out outside `<product>\<trial>\stats\<purpose>`
     pub fn root(&self) -> PathBuf {
+        if self.product.is_empty() && self.trial.is_empty() && self.purpose.is_empty() {
+            return self.root.clone();
+        }
         self.root
             .join(&self.product)
             .join(&self.trial)
             .join("stats")
             .join(&self.purpose)
     }
+
+    pub fn utility(&self) -> PathBuf {
+        self.root().join("utility")
+    }
+
+    /// all directories the investigator looks into for a kind, with the file types expected in each
+    pub fn directories(&self, kind: &Kind) -> Vec<Directory> {
+        match kind {
+            Kind::SDTM => self.sdtm_directories(),
+            Kind::ADaM => self.adam_directories(),
+            Kind::TFLs => self.tfl_directories(),
+        }
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// fallback matches recorded for item, including its supp domain
+    pub fn fallbacks(&self, item: &str) -> Vec<FallbackMatch> {
+        self.fallbacks
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|m| related(&m.item, item))
+            .cloned()
+            .collect()
+    }
+
+    /// case conflicts recorded for item, including its supp domain
+    pub fn case_conflicts(&self, item: &str) -> Vec<CaseConflict> {
+        self.conflicts
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|c| related(&c.item, item))
+            .cloned()
+            .collect()
+    }
+
+    /// drop fallback matches and case conflicts recorded for item, including its supp domain,
+    /// they are recorded again when the item is located next time
+    pub fn forget(&self, item: &str) {
+        self.fallbacks
+            .lock()
+            .unwrap()
+            .retain(|m| !related(&m.item, item));
+        self.conflicts
+            .lock()
+            .unwrap()
+            .retain(|c| !related(&c.item, item));
+    }
+
+    /// drop snapshot of directory, it will be listed again on next lookup
+    pub fn refresh(&self, dir: &Path) {
+        self.snapshots.lock().unwrap().remove(dir);
+    }
+
+    /// find file of item in directory, formats in study settings are tried in order
+    pub(crate) fn locate(
+        &self,
+        dir: PathBuf,
+        item: &str,
+        group: &Group,
+        kind: &FileType,
+    ) -> Option<File<PathBuf>> {
+        self.settings
+            .formats(kind)
+            .iter()
+            .find_map(|format| self.locate_as(&dir, item, group, kind, format))
+    }
+
+    /// find file of item saved in format, try the exact name (ignoring case) first, then the
+    /// normalized name, and the alias in study settings at last
+    fn locate_as(
+        &self,
+        dir: &Path,
+        item: &str,
+        group: &Group,
+        kind: &FileType,
+        format: &str,
+    ) -> Option<File<PathBuf>> {
+        let expected = filename_as(item, group, kind, format);
+        if let Some(file) = self.resolve(dir, item, &expected) {
+            return Some(file);
+        }
+        let (found, rule) = match self.search(dir, &expected) {
+            Some(found) => (found, MatchRule::Normalized),
+            None => {
+                let alias = filename_as(self.settings.alias(item)?, group, kind, format);
+                match self.resolve(dir, item, &alias) {
+                    Some(found) => (found, MatchRule::Alias),
+                    None => (self.search(dir, &alias)?, MatchRule::Alias),
+                }
+            }
+        };
+        self.record(FallbackMatch {
+            item: item.into(),
+            expected,
+            actual: found.name.clone(),
+            rule,
+        });
+        Some(found)
+    }
+
+    fn record(&self, fallback: FallbackMatch) {
+        let mut fallbacks = self.fallbacks.lock().unwrap();
+        if !fallbacks.contains(&fallback) {
+            fallbacks.push(fallback);
+        }
+    }
+
+    /// snapshot of directory, the directory is listed only once during the life of investigator
+    pub fn snapshot(&self, dir: &Path) -> Arc<Snapshot> {
+        let mut snapshots = self.snapshots.lock().unwrap();
+        snapshots
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| Arc::new(Snapshot::take(dir)))
+            .clone()
+    }
+
+    /// find file with the expected name ignoring case, the exact name is preferred when case variants coexist
+    fn resolve(&self, dir: &Path, item: &str, expected: &str) -> Option<File<PathBuf>> {
+        let snapshot = self.snapshot(dir);
+        let variants = snapshot
+            .names()
+            .filter(|name| name.eq_ignore_ascii_case(expected))
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+        if variants.len() > 1 {
+            let conflict = CaseConflict {
+                item: item.into(),
+                directory: dir.to_string_lossy().to_string(),
+                files: variants.clone(),
+            };
+            let mut conflicts = self.conflicts.lock().unwrap();
+            if !conflicts.contains(&conflict) {
+                conflicts.push(conflict);
+            }
+        }
+        let name = variants
+            .iter()
+            .find(|name| name.as_str().eq(expected))
+            .or(variants.first())?;
+        snapshot.file(name)
+    }
+
+    /// search directory for file with the same normalized name
+    fn search(&self, dir: &Path, expected: &str) -> Option<File<PathBuf>> {
+        let expected = normalize(expected);
+        let snapshot = self.snapshot(dir);
+        let name = snapshot
+            .names()
+            .find(|name| normalize(name).eq(&expected))?;
+        snapshot.file(name)
+    }
+}
+
+/// whether the recorded item is the item itself or its supp domain
+fn related(recorded: &str, item: &str) -> bool {
+    recorded.eq_ignore_ascii_case(item) || recorded.eq_ignore_ascii_case(&format!("supp{}", item))
+}
+
+#[derive(Debug)]
+pub struct Directory {
+    pub path: PathBuf,
+    pub group: Group,
+    pub file_types: Vec<FileType>,
 }
 
 #[derive(Debug)]
@@ -40,3 +279,86 @@ pub struct File<P: AsRef<Path>> {
     pub created_at: DateTime<Local>,
     pub modified_at: DateTime<Local>,
 }
+
+impl<P: AsRef<Path>> File<P> {
+    /// extention of file in lowercase, eg. `rtf` or `lst`
+    pub fn format(&self) -> Option<String> {
+        self.filepath
+            .as_ref()
+            .extension()
+            .map(|extention| extention.to_string_lossy().to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    #[test]
+    fn test_locate_ignore_case() -> anyhow::Result<()> {
+        let root = std::env::temp_dir().join("inspector_locate_test");
+        fs::remove_dir_all(&root).ok();
+        let dir = root.join("sdtm");
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("AE.sas"), "")?;
+        fs::write(dir.join("DM.sas"), "")?;
+        // case variants only coexist on case-sensitive filesystem
+        #[cfg(target_os = "linux")]
+        fs::write(dir.join("Ae.sas"), "")?;
+        let inv = Investigator::new(&InvestigatorParam {
+            product: "ak112".into(),
+            trial: "303".into(),
+            purpose: "CSR".into(),
+            root: root.as_path(),
+        });
+        let dm = inv.locate(dir.clone(), "dm", &Group::Production, &FileType::Code);
+        assert_eq!(dm.unwrap().name, "DM.sas");
+        assert!(inv.fallbacks("dm").is_empty());
+        assert!(inv.case_conflicts("dm").is_empty());
+        #[cfg(target_os = "linux")]
+        {
+            let ae = inv.locate(dir.clone(), "ae", &Group::Production, &FileType::Code);
+            assert!(ae.is_some());
+            assert_eq!(inv.case_conflicts("ae")[0].files, vec!["AE.sas", "Ae.sas"]);
+            inv.forget("ae");
+            assert!(inv.case_conflicts("ae").is_empty());
+        }
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_locate_formats() -> anyhow::Result<()> {
+        let root = std::env::temp_dir().join("inspector_locate_formats_test");
+        let param = InvestigatorParam {
+            product: "ak112".into(),
+            trial: "303".into(),
+            purpose: "CSR".into(),
+            root: root.as_path(),
+        };
+        let utility = Investigator::new(&param).utility();
+        fs::create_dir_all(&utility)?;
+        fs::write(
+            utility.join(SETTINGS_FILE),
+            r#"{"outputFormats":["rtf","pdf"],"qcFormats":["lst","rtf"]}"#,
+        )?;
+        let dir = root.join("tfl");
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("t-14-01-01.pdf"), "")?;
+        fs::write(dir.join("v-t-14-01-01.rtf"), "")?;
+        fs::write(dir.join("v-t-14-01-01.lst"), "")?;
+        let inv = Investigator::new(&param);
+        let output = inv.locate(
+            dir.clone(),
+            "t-14-01-01",
+            &Group::Production,
+            &FileType::Output,
+        );
+        assert_eq!(output.unwrap().name, "t-14-01-01.pdf");
+        let qc = inv.locate(dir.clone(), "t-14-01-01", &Group::Validation, &FileType::Qc);
+        assert_eq!(qc.unwrap().format(), Some("lst".into()));
+        assert!(inv.fallbacks("t-14-01-01").is_empty());
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+}
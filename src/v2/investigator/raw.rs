@@ -0,0 +1,35 @@
+use super::investigator::Investigator;
+use crate::v2::category::{Group, Kind};
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::path::PathBuf;
+
+const RAW_DIR: &str = "rawdata";
+
+/// data a kind is derived from, raw data for sdtm, sdtm datasets for adam and adam datasets for tfls
+#[derive(Debug, Serialize, Clone)]
+pub struct Upstream {
+    pub name: String,
+    pub dir: PathBuf,
+    /// modified time of the latest file in directory
+    #[serde(rename = "modifiedAt")]
+    pub modified_at: DateTime<Local>,
+}
+
+impl Investigator {
+    /// upstream data of kind, none if its directory holds no file
+    pub fn upstream(&self, kind: &Kind) -> Option<Upstream> {
+        let production = self.root().join(Group::Production.group_dir());
+        let (name, dir) = match kind {
+            Kind::SDTM => ("raw data", self.root().join(RAW_DIR)),
+            Kind::ADaM => ("sdtm", production.join(super::sdtm::data_dir())),
+            Kind::TFLs => ("adam", production.join(super::adam::data_dir())),
+        };
+        let modified_at = self.snapshot(&dir).latest()?;
+        Some(Upstream {
+            name: name.into(),
+            dir,
+            modified_at,
+        })
+    }
+}
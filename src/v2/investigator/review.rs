@@ -0,0 +1,118 @@
+use super::investigator::{File, Investigator};
+use crate::v2::category::{Group, Kind};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::PathBuf;
+
+const P21_DIR: &str = "p21";
+const REVIEW_DIR: &str = "review";
+const PDF_SUFFIX: &str = ".pdf";
+const P21_SUFFIX: &str = ".xlsx";
+/// excel keeps a lock file named after the open workbook beside it
+const LOCK_PREFIX: &str = "~$";
+
+lazy_static! {
+    /// annotated crf, eg. `AK112-303_V1.1_aCRF.pdf` or `AK112-303 aCRF v1.1.pdf`
+    static ref ACRF: Regex = Regex::new(r"(?i)(^|[^a-z])(acrf|annotated)").unwrap();
+    /// crf as a word of its own, so `acrf` and `ecrf` are not taken for it
+    static ref CRF: Regex = Regex::new(r"(?i)(^|[^a-z])(crf|case report form)([^a-z]|$)").unwrap();
+    /// documents kept beside crf which are not crf, eg. `CRF Completion Guidelines v1.0.pdf`
+    static ref CRF_GUIDE: Regex = Regex::new(r"(?i)completion|guideline|instruction|ccg").unwrap();
+}
+
+impl Investigator {
+    /// directory of trial holding documents shared by every purpose, eg. crf and specs
+    fn trial_dir(&self) -> PathBuf {
+        let root = self.root();
+        // root is `<trial>\stats\<purpose>`
+        root.ancestors()
+            .nth(2)
+            .map(|dir| dir.to_path_buf())
+            .unwrap_or(root)
+    }
+
+    pub fn crf_dir(&self) -> PathBuf {
+        self.trial_dir().join("documents").join("crf")
+    }
+
+    /// annotated crf of trial, the latest one if there are several versions
+    pub fn acrf(&self) -> Option<File<PathBuf>> {
+        latest(self, self.crf_dir(), |name| {
+            is_pdf(name) && ACRF.is_match(name)
+        })
+    }
+
+    /// blank crf of trial, a pdf named crf which is neither annotated nor a guideline of it
+    pub fn blank_crf(&self) -> Option<File<PathBuf>> {
+        latest(self, self.crf_dir(), |name| {
+            is_pdf(name) && CRF.is_match(name) && !ACRF.is_match(name) && !CRF_GUIDE.is_match(name)
+        })
+    }
+
+    pub fn p21_dir(&self) -> PathBuf {
+        self.root()
+            .join(Group::Production.group_dir())
+            .join(P21_DIR)
+    }
+
+    /// latest pinnacle 21 report of kind, a workbook whose name contains the kind,
+    /// eg. `ak112-303-sdtm-p21.xlsx`, lock files of excel (`~$...`) are skipped
+    pub fn p21_report(&self, kind: &Kind) -> Option<File<PathBuf>> {
+        let keyword = match kind {
+            Kind::SDTM => "sdtm",
+            Kind::ADaM => "adam",
+            Kind::TFLs => return None,
+        };
+        latest(self, self.p21_dir(), |name| {
+            let name = name.to_lowercase();
+            name.contains(keyword) && name.ends_with(P21_SUFFIX) && !name.starts_with(LOCK_PREFIX)
+        })
+    }
+
+    /// directory reviewers drop sign-off files of kind into, eg. `utility\review\sdtm`
+    pub fn review_dir(&self, kind: &Kind) -> PathBuf {
+        let dir = match kind {
+            Kind::SDTM => "sdtm",
+            Kind::ADaM => "adam",
+            Kind::TFLs => "tfl",
+        };
+        self.utility().join(REVIEW_DIR).join(dir)
+    }
+
+    /// sign-off file named by key in any format, eg. `ae.pdf` or `spec-v0.4.msg`
+    pub fn sign_off(&self, kind: &Kind, key: &str) -> Option<File<PathBuf>> {
+        latest(self, self.review_dir(kind), |name| {
+            let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+            stem.eq_ignore_ascii_case(key)
+        })
+    }
+
+    /// latest modified production dataset of kind, none for tfls
+    pub fn latest_dataset(&self, kind: &Kind) -> Option<File<PathBuf>> {
+        let dir = match kind {
+            Kind::SDTM => super::sdtm::data_dir(),
+            Kind::ADaM => super::adam::data_dir(),
+            Kind::TFLs => return None,
+        };
+        let dir = self.root().join(Group::Production.group_dir()).join(dir);
+        latest(self, dir, |_| true)
+    }
+}
+
+fn is_pdf(name: &str) -> bool {
+    name.to_lowercase().ends_with(PDF_SUFFIX)
+}
+
+/// latest modified file in directory whose name is accepted by filter
+fn latest<F: Fn(&str) -> bool>(
+    investigator: &Investigator,
+    dir: PathBuf,
+    filter: F,
+) -> Option<File<PathBuf>> {
+    let snapshot = investigator.snapshot(&dir);
+    snapshot
+        .names()
+        .filter(|name| filter(name))
+        .filter_map(|name| snapshot.file(name))
+        .max_by_key(|file| file.modified_at)
+}
@@ -1,129 +1,149 @@
 use super::{
-    investigator::{File, Investigator},
-    utils::filename,
+    investigator::{Directory, File, Investigator},
+    utils::extention,
 };
-use crate::v2::{
-    category::{FileType, Group},
-    investigator::utils::file,
-};
-use std::path::PathBuf;
+use crate::v2::category::{FileType, Group};
+use std::path::{Path, PathBuf};
+
+fn code_dir() -> PathBuf {
+    Path::new("program").join("sdtm")
+}
 
-const CODE_DIR: &str = r"program\sdtm";
-const DATA_DIR: &str = r"dataset\sdtm";
-const QC_DIR: &str = r"qc-result\sdtm";
+pub(super) fn data_dir() -> PathBuf {
+    Path::new("dataset").join("sdtm")
+}
+
+fn qc_dir() -> PathBuf {
+    Path::new("qc-result").join("sdtm")
+}
 
 impl Investigator {
+    pub fn sdtm_directories(&self) -> Vec<Directory> {
+        let production = self.root().join(Group::Production.group_dir());
+        let validation = self.root().join(Group::Validation.group_dir());
+        vec![
+            Directory {
+                path: production.join(code_dir()),
+                group: Group::Production,
+                file_types: vec![FileType::Code, FileType::Log],
+            },
+            Directory {
+                path: production.join(data_dir()),
+                group: Group::Production,
+                file_types: vec![FileType::Data, FileType::Xpt],
+            },
+            Directory {
+                path: validation.join(code_dir()),
+                group: Group::Validation,
+                file_types: vec![FileType::Code, FileType::Log],
+            },
+            Directory {
+                path: validation.join(data_dir()),
+                group: Group::Validation,
+                file_types: vec![FileType::Data],
+            },
+            Directory {
+                path: validation.join(qc_dir()),
+                group: Group::Validation,
+                file_types: vec![FileType::Qc],
+            },
+        ]
+    }
+
     pub fn sdtm_code_production(&self, item: &str) -> Option<File<PathBuf>> {
         let group = Group::Production;
-        let filepath = self
-            .root()
-            .join(group.group_dir())
-            .join(CODE_DIR)
-            .join(filename(item, &group, &FileType::Code));
-        file(filepath)
+        let dir = self.root().join(group.group_dir()).join(code_dir());
+        self.locate(dir, item, &group, &FileType::Code)
     }
 
     pub fn sdtm_code_validation(&self, item: &str) -> Option<File<PathBuf>> {
         let group = Group::Validation;
-        let filepath = self
-            .root()
-            .join(group.group_dir())
-            .join(CODE_DIR)
-            .join(filename(item, &group, &FileType::Code));
-        file(filepath)
+        let dir = self.root().join(group.group_dir()).join(code_dir());
+        self.locate(dir, item, &group, &FileType::Code)
     }
 
     pub fn sdtm_data_main_production(&self, item: &str) -> Option<File<PathBuf>> {
         let group = Group::Production;
-        let filepath = self
-            .root()
-            .join(group.group_dir())
-            .join(DATA_DIR)
-            .join(filename(item, &group, &FileType::Data));
-        file(filepath)
+        let dir = self.root().join(group.group_dir()).join(data_dir());
+        self.locate(dir, item, &group, &FileType::Data)
     }
 
     pub fn sdtm_data_main_validation(&self, item: &str) -> Option<File<PathBuf>> {
         let group = Group::Validation;
-        let filepath = self
-            .root()
-            .join(group.group_dir())
-            .join(DATA_DIR)
-            .join(filename(item, &group, &FileType::Data));
-        file(filepath)
+        let dir = self.root().join(group.group_dir()).join(data_dir());
+        self.locate(dir, item, &group, &FileType::Data)
     }
 
     pub fn sdtm_data_supp_production(&self, item: &str) -> Option<File<PathBuf>> {
         let group = Group::Production;
         let item = format!("supp{}", item);
-        let filepath = self
+        let dir = self
             .root()
             .join(Group::Production.group_dir())
-            .join(DATA_DIR)
-            .join(filename(&item, &group, &FileType::Data));
-        file(filepath)
+            .join(data_dir());
+        self.locate(dir, &item, &group, &FileType::Data)
     }
 
     pub fn sdtm_data_supp_validation(&self, item: &str) -> Option<File<PathBuf>> {
         let group = Group::Validation;
         let item = format!("supp{}", item);
-        let filepath = self
-            .root()
-            .join(group.group_dir())
-            .join(DATA_DIR)
-            .join(filename(&item, &group, &FileType::Data));
-        file(filepath)
+        let dir = self.root().join(group.group_dir()).join(data_dir());
+        self.locate(dir, &item, &group, &FileType::Data)
     }
 
     pub fn sdtm_xpt_main(&self, item: &str) -> Option<File<PathBuf>> {
         let group = Group::Production;
-        let filepath = self
-            .root()
-            .join(group.group_dir())
-            .join(DATA_DIR)
-            .join(filename(&item, &group, &FileType::Xpt));
-        file(filepath)
+        let dir = self.root().join(group.group_dir()).join(data_dir());
+        self.locate(dir, item, &group, &FileType::Xpt)
     }
 
     pub fn sdtm_xpt_supp(&self, item: &str) -> Option<File<PathBuf>> {
         let group = Group::Production;
         let item = format!("supp{}", item);
-        let filepath = self
+        let dir = self
             .root()
             .join(Group::Production.group_dir())
-            .join(DATA_DIR)
-            .join(filename(&item, &group, &FileType::Xpt));
-        file(filepath)
+            .join(data_dir());
+        self.locate(dir, &item, &group, &FileType::Xpt)
     }
 
-    pub fn sdtm_log(&self, item: &str, group: &Group) -> Option<File<PathBuf>> {
-        let filepath = self
+    /// supp datasets and transport files in production data directory, eg. `suppae.sas7bdat`
+    pub fn sdtm_supp_files(&self) -> Vec<File<PathBuf>> {
+        let dir = self
             .root()
-            .join(group.group_dir())
-            .join(CODE_DIR)
-            .join(filename(&item, &group, &FileType::Log));
-        file(filepath)
+            .join(Group::Production.group_dir())
+            .join(data_dir());
+        let snapshot = self.snapshot(&dir);
+        let suffixes = [FileType::Data, FileType::Xpt]
+            .iter()
+            .map(|file_type| format!(".{}", extention(file_type)))
+            .collect::<Vec<_>>();
+        snapshot
+            .names()
+            .filter(|name| {
+                let name = name.to_lowercase();
+                name.starts_with("supp") && suffixes.iter().any(|suffix| name.ends_with(suffix))
+            })
+            .filter_map(|name| snapshot.file(name))
+            .collect()
+    }
+
+    pub fn sdtm_log(&self, item: &str, group: &Group) -> Option<File<PathBuf>> {
+        let dir = self.root().join(group.group_dir()).join(code_dir());
+        self.locate(dir, item, group, &FileType::Log)
     }
 
     pub fn sdtm_qc_main(&self, item: &str) -> Option<File<PathBuf>> {
         let group = Group::Validation;
-        let filepath = self
-            .root()
-            .join(group.group_dir())
-            .join(QC_DIR)
-            .join(filename(&item, &group, &FileType::Qc));
-        file(filepath)
+        let dir = self.root().join(group.group_dir()).join(qc_dir());
+        self.locate(dir, item, &group, &FileType::Qc)
     }
 
     pub fn sdtm_qc_supp(&self, item: &str) -> Option<File<PathBuf>> {
         let group = Group::Validation;
         let item = format!("supp{}", item);
-        let filepath = self
-            .root()
-            .join(group.group_dir())
-            .join(QC_DIR)
-            .join(filename(&item, &group, &FileType::Qc));
-        file(filepath)
+        let dir = self.root().join(group.group_dir()).join(qc_dir());
+        self.locate(dir, &item, &group, &FileType::Qc)
     }
 }
 
@@ -0,0 +1,116 @@
+use super::utils::extention;
+use crate::v2::{
+    category::FileType,
+    error::{Cause, Error, Result},
+};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::{collections::HashMap, fs, io::ErrorKind, path::Path};
+
+pub const SETTINGS_FILE: &str = "inspector.json";
+
+/// per-study settings, read from `inspector.json` under utility folder, eg.
+///
+/// ```json
+/// {
+///     "alias": { "t-14-01-01": "t_14_1_1" },
+///     "dataCut": "2024-03-01",
+///     "failReviewOnLint": true,
+///     "outputFormats": ["rtf", "pdf"],
+///     "qcFormats": ["rtf", "lst"],
+///     "bundle": "all-tfls.pdf"
+/// }
+/// ```
+#[derive(Debug, Default, Deserialize)]
+pub struct Settings {
+    /// config item => base name of files actually saved
+    #[serde(default)]
+    pub alias: HashMap<String, String>,
+    /// outputs dated before it are produced from an older data cut
+    #[serde(rename = "dataCut", default)]
+    pub data_cut: Option<NaiveDate>,
+    /// findings of output lint block output review stage
+    #[serde(rename = "failReviewOnLint", default)]
+    pub fail_review_on_lint: bool,
+    /// extentions of outputs in the order they are looked for, `rtf` if empty
+    #[serde(rename = "outputFormats", default)]
+    pub output_formats: Vec<String>,
+    /// extentions of qc results in the order they are looked for, `rtf` if empty
+    #[serde(rename = "qcFormats", default)]
+    pub qc_formats: Vec<String>,
+    /// file name of the combined output of all tfls, found by its name if not given
+    #[serde(default)]
+    pub bundle: Option<String>,
+}
+
+impl Settings {
+    /// settings file is optional, fallback to default only if it is missing
+    pub fn read<P: AsRef<Path>>(file: P) -> Result<Settings> {
+        let file = file.as_ref();
+        let failed = |source: Cause| Error::ReadSettingsFailed {
+            path: file.to_path_buf(),
+            source,
+        };
+        let content = match fs::read_to_string(file) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Settings::default()),
+            Err(e) => return Err(failed(Box::new(e))),
+        };
+        serde_json::from_str(&content).map_err(|e| failed(Box::new(e)))
+    }
+
+    pub fn alias(&self, item: &str) -> Option<&str> {
+        self.alias
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(item))
+            .map(|(_, alias)| alias.as_str())
+    }
+
+    /// extentions files of kind are looked for with, in order of preference
+    pub fn formats(&self, kind: &FileType) -> Vec<String> {
+        let formats = match kind {
+            FileType::Output => &self.output_formats,
+            FileType::Qc => &self.qc_formats,
+            _ => return vec![extention(kind).into()],
+        };
+        let mut found: Vec<String> = vec![];
+        for format in formats.iter() {
+            let format = format.trim().trim_start_matches('.').to_lowercase();
+            if !format.is_empty() && !found.contains(&format) {
+                found.push(format);
+            }
+        }
+        if found.is_empty() {
+            found.push(extention(kind).into());
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_settings_alias() -> anyhow::Result<()> {
+        let settings: Settings =
+            serde_json::from_str(r#"{"alias":{"T-14-01-01":"t_14_1_1"},"dataCut":"2024-03-01"}"#)?;
+        assert_eq!(settings.alias("t-14-01-01"), Some("t_14_1_1"));
+        assert_eq!(settings.data_cut, NaiveDate::from_ymd_opt(2024, 3, 1));
+        assert!(!settings.fail_review_on_lint);
+        assert_eq!(settings.alias("t-14-01-02"), None);
+        assert_eq!(settings.formats(&FileType::Output), vec!["rtf"]);
+        let settings: Settings =
+            serde_json::from_str(r#"{"outputFormats":["PDF",".docx"],"qcFormats":["lst","txt"]}"#)?;
+        assert_eq!(settings.formats(&FileType::Output), vec!["pdf", "docx"]);
+        assert_eq!(settings.formats(&FileType::Qc), vec!["lst", "txt"]);
+        assert_eq!(settings.formats(&FileType::Log), vec!["log"]);
+        let settings = Settings::read(Path::new("not-exist.json"))?;
+        assert!(settings.alias.is_empty());
+        let file = std::env::temp_dir().join("inspector_settings_test.json");
+        std::fs::write(&file, r#"{"alias":["t-14-01-01"]}"#)?;
+        let error = Settings::read(&file);
+        std::fs::remove_file(&file)?;
+        assert!(matches!(error, Err(Error::ReadSettingsFailed { .. })));
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use super::{investigator::File, utils::system_time_to_chrono};
+use chrono::{DateTime, Local};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// files of one directory with their timestamps, taken with a single listing
+/// so that looking up files does not stat each of them again
+#[derive(Debug, Default)]
+pub struct Snapshot {
+    dir: PathBuf,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    name: String,
+    created_at: Option<DateTime<Local>>,
+    modified_at: Option<DateTime<Local>>,
+}
+
+impl Snapshot {
+    /// list files in directory, sub directories are ignored and a missing directory gives an empty snapshot
+    pub fn take<P: AsRef<Path>>(dir: P) -> Snapshot {
+        let dir = dir.as_ref().to_path_buf();
+        let mut entries = vec![];
+        if let Ok(read_dir) = fs::read_dir(&dir) {
+            for entry in read_dir.filter_map(|entry| entry.ok()) {
+                // windows returns metadata with the listing, no file is opened for it
+                let metadata = match entry.metadata() {
+                    Ok(metadata) => metadata,
+                    Err(_) => continue,
+                };
+                if metadata.is_dir() {
+                    continue;
+                }
+                entries.push(Entry {
+                    name: entry.file_name().to_string_lossy().to_string(),
+                    created_at: metadata.created().ok().map(|t| system_time_to_chrono(&t)),
+                    modified_at: metadata.modified().ok().map(|t| system_time_to_chrono(&t)),
+                });
+            }
+        }
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Snapshot { dir, entries }
+    }
+
+    /// file names in the directory, sorted
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|entry| entry.name.as_str())
+    }
+
+    /// latest modified time of files in the directory, none if it has no file
+    pub fn latest(&self) -> Option<DateTime<Local>> {
+        self.entries
+            .iter()
+            .filter_map(|entry| entry.modified_at)
+            .max()
+    }
+
+    /// file with exactly the name, none if missing or its timestamps are unavailable
+    pub fn file(&self, name: &str) -> Option<File<PathBuf>> {
+        let index = self
+            .entries
+            .binary_search_by(|entry| entry.name.as_str().cmp(name))
+            .ok()?;
+        let entry = &self.entries[index];
+        Some(File {
+            name: entry.name.clone(),
+            filepath: self.dir.join(&entry.name),
+            created_at: entry.created_at?,
+            modified_at: entry.modified_at?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_snapshot() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join("inspector_snapshot_test");
+        fs::create_dir_all(dir.join("archive"))?;
+        fs::write(dir.join("dm.sas"), "")?;
+        fs::write(dir.join("ae.sas"), "")?;
+        let snapshot = Snapshot::take(&dir);
+        assert_eq!(
+            snapshot.names().collect::<Vec<_>>(),
+            vec!["ae.sas", "dm.sas"]
+        );
+        assert_eq!(
+            snapshot.file("ae.sas").unwrap().filepath,
+            dir.join("ae.sas")
+        );
+        assert!(snapshot.file("archive").is_none());
+        let latest = ["ae.sas", "dm.sas"]
+            .iter()
+            .filter_map(|name| snapshot.file(name))
+            .map(|file| file.modified_at)
+            .max();
+        assert_eq!(snapshot.latest(), latest);
+        let missing = Snapshot::take(dir.join("not-exist"));
+        assert!(missing.names().next().is_none());
+        assert!(missing.latest().is_none());
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    /// compare snapshot lookup with stat-ing each file, run with `cargo test -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn test_snapshot_timing() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join("inspector_snapshot_timing");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir)?;
+        let names = (0..5000)
+            .map(|i| format!("t-14-{:02}-{:02}.rtf", i / 100, i % 100))
+            .collect::<Vec<_>>();
+        for name in names.iter() {
+            fs::write(dir.join(name), "")?;
+        }
+        let start = Instant::now();
+        let found = names
+            .iter()
+            .filter_map(|name| fs::metadata(dir.join(name)).ok())
+            .count();
+        let stat = start.elapsed();
+        assert_eq!(found, names.len());
+        let start = Instant::now();
+        let snapshot = Snapshot::take(&dir);
+        let found = names.iter().filter_map(|name| snapshot.file(name)).count();
+        let listing = start.elapsed();
+        assert_eq!(found, names.len());
+        println!(
+            "{} files, stat each: {:?}, snapshot: {:?}",
+            names.len(),
+            stat,
+            listing
+        );
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
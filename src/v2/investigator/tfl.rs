@@ -1,58 +1,147 @@
 use super::{
-    investigator::{File, Investigator},
-    utils::{file, filename},
+    investigator::{Directory, File, Investigator},
+    utils::{normalize, OUTPUT_EXTENTION},
 };
 use crate::v2::category::{FileType, Group};
-use std::path::PathBuf;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::{Path, PathBuf};
 
-const CODE_DIR: &str = r"program\tfl";
-const DATA_DIR: &str = r"dataset\tfl";
-const QC_DIR: &str = r"qc-result\tfl";
 const OUTPUT_DIR: &str = r"output";
 
+fn code_dir() -> PathBuf {
+    Path::new("program").join("tfl")
+}
+
+fn data_dir() -> PathBuf {
+    Path::new("dataset").join("tfl")
+}
+
+fn qc_dir() -> PathBuf {
+    Path::new("qc-result").join("tfl")
+}
+
+/// formats a bundle can be read in
+const BUNDLE_FORMATS: [&str; 2] = ["rtf", "pdf"];
+
+lazy_static! {
+    /// normalized stem of the combined output of all tfls, the study may prefix it,
+    /// eg. `all-tfls`, `ak112-303-combined-outputs` or `tfl-bundle`
+    static ref BUNDLE_NAME: Regex = Regex::new(
+        r"^(?:[a-z0-9]+-)*(?:(?:all|combined)-(?:tfls?|outputs?)|(?:tfls?-)?bundle)$"
+    )
+    .unwrap();
+    /// stem of a single output, eg. `t-14-2-1-combined-arms`, never taken for the bundle
+    static ref OUTPUT_NAME: Regex = Regex::new(r"^[tlf]-\d").unwrap();
+}
+
 impl Investigator {
+    pub fn tfl_directories(&self) -> Vec<Directory> {
+        let production = self.root().join(Group::Production.group_dir());
+        let validation = self.root().join(Group::Validation.group_dir());
+        vec![
+            Directory {
+                path: production.join(code_dir()),
+                group: Group::Production,
+                file_types: vec![FileType::Code, FileType::Log],
+            },
+            Directory {
+                path: production.join(data_dir()),
+                group: Group::Production,
+                file_types: vec![FileType::Data],
+            },
+            Directory {
+                path: production.join(OUTPUT_DIR),
+                group: Group::Production,
+                file_types: vec![FileType::Output],
+            },
+            Directory {
+                path: validation.join(code_dir()),
+                group: Group::Validation,
+                file_types: vec![FileType::Code, FileType::Log],
+            },
+            Directory {
+                path: validation.join(data_dir()),
+                group: Group::Validation,
+                file_types: vec![FileType::Data],
+            },
+            Directory {
+                path: validation.join(qc_dir()),
+                group: Group::Validation,
+                file_types: vec![FileType::Qc],
+            },
+        ]
+    }
+
     pub fn tfl_code(&self, item: &str, group: &Group) -> Option<File<PathBuf>> {
-        let filepath = self
-            .root()
-            .join(group.group_dir())
-            .join(CODE_DIR)
-            .join(filename(item, group, &FileType::Code));
-        file(filepath)
+        let dir = self.root().join(group.group_dir()).join(code_dir());
+        self.locate(dir, item, group, &FileType::Code)
     }
 
     pub fn tfl_data(&self, item: &str, group: &Group) -> Option<File<PathBuf>> {
-        let filepath = self
-            .root()
-            .join(group.group_dir())
-            .join(DATA_DIR)
-            .join(filename(item, group, &FileType::Data));
-        file(filepath)
+        let dir = self.root().join(group.group_dir()).join(data_dir());
+        self.locate(dir, item, group, &FileType::Data)
     }
 
     pub fn tfl_log(&self, item: &str, group: &Group) -> Option<File<PathBuf>> {
-        let filepath = self
-            .root()
-            .join(group.group_dir())
-            .join(CODE_DIR)
-            .join(filename(item, group, &FileType::Log));
-        file(filepath)
+        let dir = self.root().join(group.group_dir()).join(code_dir());
+        self.locate(dir, item, group, &FileType::Log)
     }
 
     pub fn tfl_qc_result(&self, item: &str) -> Option<File<PathBuf>> {
-        let filepath = self
+        let dir = self
             .root()
             .join(Group::Validation.group_dir())
-            .join(QC_DIR)
-            .join(filename(item, &Group::Validation, &FileType::Qc));
-        file(filepath)
+            .join(qc_dir());
+        self.locate(dir, item, &Group::Validation, &FileType::Qc)
     }
 
     pub fn tfl_output(&self, item: &str, group: &Group) -> Option<File<PathBuf>> {
-        let filepath = self
+        let dir = self.root().join(group.group_dir()).join(OUTPUT_DIR);
+        self.locate(dir, item, group, &FileType::Output)
+    }
+
+    /// every rtf in production output directory, whether top lists it or not
+    pub fn tfl_rtf_outputs(&self) -> Vec<File<PathBuf>> {
+        let dir = self
+            .root()
+            .join(Group::Production.group_dir())
+            .join(OUTPUT_DIR);
+        let snapshot = self.snapshot(&dir);
+        let extention = format!(".{}", OUTPUT_EXTENTION);
+        snapshot
+            .names()
+            .filter(|name| name.to_lowercase().ends_with(&extention))
+            .filter_map(|name| snapshot.file(name))
+            .collect()
+    }
+
+    /// combined output of all tfls in production output directory, the name in study settings
+    /// is preferred, otherwise the latest rtf or pdf named like a bundle, eg. `all-tfls.pdf`
+    pub fn tfl_bundle(&self) -> Option<File<PathBuf>> {
+        let dir = self
             .root()
-            .join(group.group_dir())
-            .join(OUTPUT_DIR)
-            .join(filename(item, group, &FileType::Output));
-        file(filepath)
+            .join(Group::Production.group_dir())
+            .join(OUTPUT_DIR);
+        let snapshot = self.snapshot(&dir);
+        if let Some(bundle) = self.settings().bundle.as_deref() {
+            let name = snapshot
+                .names()
+                .find(|name| name.eq_ignore_ascii_case(bundle))?;
+            return snapshot.file(name);
+        }
+        snapshot
+            .names()
+            .filter(|name| {
+                let name = normalize(name).replace(' ', "-");
+                let Some((stem, format)) = name.rsplit_once('.') else {
+                    return false;
+                };
+                BUNDLE_FORMATS.contains(&format)
+                    && BUNDLE_NAME.is_match(stem)
+                    && !OUTPUT_NAME.is_match(stem)
+            })
+            .filter_map(|name| snapshot.file(name))
+            .max_by_key(|file| file.modified_at)
     }
 }
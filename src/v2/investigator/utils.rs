@@ -1,11 +1,6 @@
-use super::investigator::File;
 use crate::v2::{category::FileType, Group};
 use chrono::{DateTime, Local};
-use std::{
-    fs,
-    path::Path,
-    time::{SystemTime, UNIX_EPOCH},
-};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub const CODE_FILE_EXTENTION: &str = "sas";
 pub const OUTPUT_EXTENTION: &str = "rtf";
@@ -14,7 +9,7 @@ pub const XPT_FILE_EXTENTION: &str = "xpt";
 pub const LOG_FILE_EXTENTION: &str = "log";
 pub const QC_FILE_EXTENTION: &str = "rtf";
 
-fn system_time_to_chrono(source: &SystemTime) -> DateTime<Local> {
+pub fn system_time_to_chrono(source: &SystemTime) -> DateTime<Local> {
     let duration_since_epoch = source
         .duration_since(UNIX_EPOCH)
         .expect("Time went backwards");
@@ -24,29 +19,29 @@ fn system_time_to_chrono(source: &SystemTime) -> DateTime<Local> {
     naive.unwrap_or_default().with_timezone(&Local)
 }
 
-pub fn file<P: AsRef<Path>>(filepath: P) -> Option<File<P>> {
-    let name = filepath.as_ref().file_name()?.to_string_lossy().to_string();
-    match fs::metadata(filepath.as_ref().to_path_buf()) {
-        Ok(metadata) => Some(File {
-            name,
-            filepath,
-            created_at: system_time_to_chrono(&metadata.created().ok()?),
-            modified_at: system_time_to_chrono(&metadata.modified().ok()?),
-        }),
-        Err(_) => None,
-    }
+/// normalize file name for fuzzy matching, ignore case and treat `_` as `-`
+pub fn normalize(name: &str) -> String {
+    name.to_lowercase().replace('_', "-")
 }
 
-pub fn filename(item: &str, group: &Group, kind: &FileType) -> String {
-    let item = item.to_lowercase();
-    let extention = match kind {
+pub fn extention(kind: &FileType) -> &'static str {
+    match kind {
         FileType::Code => CODE_FILE_EXTENTION,
         FileType::Data => DATA_FILE_EXTENTION,
         FileType::Xpt => XPT_FILE_EXTENTION,
         FileType::Output => OUTPUT_EXTENTION,
         FileType::Log => LOG_FILE_EXTENTION,
         FileType::Qc => QC_FILE_EXTENTION,
-    };
+    }
+}
+
+pub fn filename(item: &str, group: &Group, kind: &FileType) -> String {
+    filename_as(item, group, kind, extention(kind))
+}
+
+/// file name of item saved in the given format, outputs and qc results may come in several formats
+pub fn filename_as(item: &str, group: &Group, kind: &FileType, extention: &str) -> String {
+    let item = item.to_lowercase();
     let filename = match group {
         Group::Production => format!("{}.{}", item, extention),
         Group::Validation => format!("v-{}.{}", item, extention),
@@ -0,0 +1,310 @@
+use super::{
+    category::{FileType, Kind},
+    config::reader::{read_config, Config},
+    error::Result,
+    inspect::{
+        evidence::Evidence,
+        inspector::{cache, inspector, InspectOption},
+        result::{IndividualResult, InspectionResult, Status},
+        timeline::{timeline, TimelineEntry},
+    },
+    investigator::{
+        investigator::{Investigator, InvestigatorParam},
+        raw::Upstream,
+    },
+    sequence::audit::{self, AuditResult, SequenceResult},
+};
+use serde::Serialize;
+use std::path::Path;
+
+/// stage a group of files is in, from creating code to passing qc
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub enum GroupStatus {
+    Building,
+    Unexpected,
+    Changed,
+    Ready,
+    NotMatch,
+    Pass,
+    NotApplicable,
+    NotStart,
+}
+
+impl GroupStatus {
+    /// status which comes with evidence to explain it
+    pub fn explainable(&self) -> bool {
+        matches!(self, GroupStatus::Unexpected | GroupStatus::Changed)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct GroupLifecycle {
+    pub status: GroupStatus,
+    /// why status is `Unexpected` or `Changed`
+    pub evidence: Option<Evidence>,
+    pub files: Vec<SequenceResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Lifecycle {
+    pub item: String,
+    pub upstream: Option<Upstream>,
+    /// upstream data and existing files of both groups, earliest first
+    pub timeline: Vec<TimelineEntry>,
+    pub production: GroupLifecycle,
+    pub validation: GroupLifecycle,
+    pub result: InspectionResult,
+}
+
+/// inspect items of config and place each group of them in its lifecycle
+pub fn lifecycle<P: AsRef<Path>>(
+    param: &InvestigatorParam<P>,
+    config_file: P,
+    kind: &Kind,
+    option: &InspectOption,
+) -> Result<Vec<Lifecycle>> {
+    let config = read_config(config_file, kind)?;
+    let investigator = Investigator::try_new(param)?;
+    let cache = cache(&investigator, option);
+    let inspector = inspector(kind, investigator, &config, option, cache.clone());
+    let results = inspector.inspect()?;
+    cache.save().ok();
+    // directories are listed once by the inspector, auditing again only reads its snapshots
+    let investigator = inspector.investigator();
+    let upstream = investigator.upstream(kind);
+    Ok(config
+        .iter()
+        .zip(results)
+        .map(|(item, result)| {
+            let audits = audit::new(&item.name, kind, item.supp, investigator).audit();
+            Lifecycle::new(item, result, audits, upstream.as_ref())
+        })
+        .collect())
+}
+
+impl Lifecycle {
+    pub fn new(
+        item: &Config,
+        result: InspectionResult,
+        audits: Vec<AuditResult>,
+        upstream: Option<&Upstream>,
+    ) -> Lifecycle {
+        let mut audits = audits.into_iter().map(|audit| audit.sequences);
+        let production = audits.next().unwrap_or_default();
+        let validation = audits.next().unwrap_or_default();
+        let (status, evidence) = develop(&production, &result.production_result, upstream);
+        let production = GroupLifecycle {
+            status,
+            evidence,
+            files: production,
+        };
+        let (status, evidence) = match item.qc {
+            true => validate(&validation, &result, upstream),
+            false => (GroupStatus::NotApplicable, None),
+        };
+        let validation = GroupLifecycle {
+            status,
+            evidence,
+            files: validation,
+        };
+        Lifecycle {
+            item: result.item.clone(),
+            upstream: upstream.cloned(),
+            timeline: timeline(upstream, production.files.iter().chain(&validation.files)),
+            production,
+            validation,
+            result,
+        }
+    }
+}
+
+/// status of a group before qc, production group never goes further than `Ready`
+fn develop(
+    files: &[SequenceResult],
+    individual: &IndividualResult,
+    upstream: Option<&Upstream>,
+) -> (GroupStatus, Option<Evidence>) {
+    let files = files
+        .iter()
+        .filter(|file| file.kind.ne(&FileType::Qc))
+        .collect::<Vec<_>>();
+    let code_exists = files
+        .iter()
+        .any(|file| file.kind.eq(&FileType::Code) && file.status.ne(&Status::Missing));
+    if code_exists && !individual.start_coding {
+        return (GroupStatus::NotStart, None);
+    }
+    if files.iter().any(|file| file.status.eq(&Status::Missing)) {
+        return (GroupStatus::Building, None);
+    }
+    if let Some(upstream) = upstream {
+        let stale = files.iter().find_map(|file| match file.modified_at {
+            Some(modified_at)
+                if file.kind.ne(&FileType::Code) && modified_at.lt(&upstream.modified_at) =>
+            {
+                Some(Evidence::upstream(&file.name, modified_at, upstream))
+            }
+            _ => None,
+        });
+        if stale.is_some() {
+            return (GroupStatus::Changed, stale);
+        }
+    }
+    match files
+        .iter()
+        .find(|file| matches!(file.status, Status::Failed(_)))
+    {
+        Some(file) => (GroupStatus::Unexpected, file.evidence.clone()),
+        None => (GroupStatus::Ready, None),
+    }
+}
+
+/// status of validation group, qc is only judged once the datasets are ready
+fn validate(
+    files: &[SequenceResult],
+    result: &InspectionResult,
+    upstream: Option<&Upstream>,
+) -> (GroupStatus, Option<Evidence>) {
+    let (status, evidence) = develop(files, &result.validation_result, upstream);
+    if status.ne(&GroupStatus::Ready) {
+        return (status, evidence);
+    }
+    let qc_files = files.iter().filter(|file| file.kind.eq(&FileType::Qc));
+    let mut outdated = None;
+    for file in qc_files {
+        match file.status {
+            Status::Missing => return (GroupStatus::Building, None),
+            Status::Failed(_) if outdated.is_none() => outdated = Some(file.evidence.clone()),
+            _ => {}
+        }
+    }
+    if let Some(evidence) = outdated {
+        return (GroupStatus::Unexpected, evidence);
+    }
+    let qc = [Some(&result.qc), result.qc_supp.as_ref()]
+        .into_iter()
+        .flatten()
+        .min_by_key(|status| status.severity());
+    match qc {
+        Some(Status::Pass) => (GroupStatus::Pass, None),
+        Some(Status::Failed(_)) => (GroupStatus::NotMatch, None),
+        _ => (GroupStatus::Ready, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::{category::Group, inspect::evidence::Rule};
+    use chrono::{DateTime, Local, TimeZone};
+    use std::path::PathBuf;
+
+    fn time(hour: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 3, 1, hour, 0, 0).unwrap()
+    }
+
+    fn file(name: &str, kind: FileType, group: Group, hour: Option<u32>) -> SequenceResult {
+        SequenceResult {
+            name: name.into(),
+            kind,
+            status: match hour {
+                Some(_) => Status::Pass,
+                None => Status::Missing,
+            },
+            group,
+            modified_at: hour.map(time),
+            evidence: None,
+        }
+    }
+
+    fn audits(qc_hour: Option<u32>) -> Vec<AuditResult> {
+        let production = vec![
+            file("ae.sas", FileType::Code, Group::Production, Some(8)),
+            file("ae.sas7bdat", FileType::Data, Group::Production, Some(9)),
+            file("ae.log", FileType::Log, Group::Production, Some(10)),
+        ];
+        let validation = vec![
+            file("v-ae.sas", FileType::Code, Group::Validation, Some(8)),
+            file("v_ae.sas7bdat", FileType::Data, Group::Validation, Some(9)),
+            file("v-ae.rtf", FileType::Qc, Group::Validation, qc_hour),
+            file("v-ae.log", FileType::Log, Group::Validation, Some(11)),
+        ];
+        [production, validation]
+            .into_iter()
+            .map(|sequences| AuditResult {
+                sequences,
+                status: Status::Pass,
+            })
+            .collect()
+    }
+
+    fn result(qc: Status) -> InspectionResult {
+        let individual = || IndividualResult {
+            start_coding: true,
+            log: Status::Pass,
+            sequence: Status::Pass,
+        };
+        InspectionResult {
+            item: "ae".into(),
+            qc,
+            production_result: individual(),
+            validation_result: individual(),
+            ..Default::default()
+        }
+    }
+
+    fn upstream(hour: u32) -> Upstream {
+        Upstream {
+            name: "raw data".into(),
+            dir: PathBuf::from("rawdata"),
+            modified_at: time(hour),
+        }
+    }
+
+    #[test]
+    fn test_lifecycle() {
+        let item = Config {
+            name: "ae".into(),
+            ..Default::default()
+        };
+        let raw = upstream(7);
+        let lifecycle = Lifecycle::new(&item, result(Status::Pass), audits(Some(10)), Some(&raw));
+        assert_eq!(lifecycle.production.status, GroupStatus::Ready);
+        assert_eq!(lifecycle.validation.status, GroupStatus::Pass);
+        assert_eq!(lifecycle.timeline.len(), 8);
+        assert_eq!(lifecycle.timeline[0].name, "raw data");
+        assert_eq!(lifecycle.timeline[7].name, "v-ae.log");
+
+        let lifecycle = Lifecycle::new(
+            &item,
+            result(Status::Failed("Value differs".into())),
+            audits(Some(10)),
+            None,
+        );
+        assert_eq!(lifecycle.validation.status, GroupStatus::NotMatch);
+        assert_eq!(lifecycle.timeline.len(), 7);
+
+        let lifecycle = Lifecycle::new(&item, result(Status::Missing), audits(None), None);
+        assert_eq!(lifecycle.validation.status, GroupStatus::Building);
+
+        let raw = upstream(10);
+        let lifecycle = Lifecycle::new(&item, result(Status::Pass), audits(Some(10)), Some(&raw));
+        assert_eq!(lifecycle.production.status, GroupStatus::Changed);
+        let evidence = lifecycle.production.evidence.unwrap();
+        assert_eq!(evidence.rule, Rule::UpstreamChanged);
+        assert_eq!(evidence.base, PathBuf::from("ae.sas7bdat"));
+        assert!(GroupStatus::Changed.explainable());
+        assert!(!GroupStatus::Building.explainable());
+
+        let item = Config {
+            name: "ae".into(),
+            qc: false,
+            ..Default::default()
+        };
+        let mut result = result(Status::Missing);
+        result.production_result.start_coding = false;
+        let lifecycle = Lifecycle::new(&item, result, audits(None), None);
+        assert_eq!(lifecycle.production.status, GroupStatus::NotStart);
+        assert_eq!(lifecycle.validation.status, GroupStatus::NotApplicable);
+    }
+}
@@ -0,0 +1,253 @@
+use super::{
+    category::{FileType, Group, Kind},
+    config::reader::{read_config, Config},
+    error::Result,
+    investigator::{
+        investigator::{Investigator, InvestigatorParam},
+        utils::{filename_as, normalize},
+    },
+};
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::{collections::HashSet, path::Path};
+
+const VALIDATION_PREFIXES: [&str; 2] = ["v-", "v_"];
+
+#[derive(Debug, Serialize)]
+pub struct OrphanGroup {
+    pub directory: String,
+    pub group: Group,
+    #[serde(rename = "fileType")]
+    pub file_type: FileType,
+    pub files: Vec<OrphanFile>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrphanFile {
+    pub name: String,
+    /// the config item whose name is closest to the file
+    pub suggestion: Option<String>,
+    #[serde(rename = "modifiedAt")]
+    pub modified_at: Option<DateTime<Local>>,
+}
+
+/// list files in directories of a kind which do not belong to any item in config,
+/// grouped by directory and file type
+pub fn orphan_detail<P: AsRef<Path>>(
+    param: &InvestigatorParam<P>,
+    config_file: P,
+    kind: &Kind,
+) -> Result<Vec<OrphanGroup>> {
+    let config = read_config(config_file, kind)?;
+    let investigator = Investigator::try_new(param)?;
+    let candidates = candidates(&config, kind);
+    // files saved under alias name belong to config item as well
+    let aliases = candidates
+        .iter()
+        .filter_map(|item| investigator.settings().alias(item))
+        .map(|alias| alias.to_lowercase())
+        .collect::<Vec<_>>();
+    // the combined output of all tfls belongs to no single item
+    let bundle = match kind {
+        Kind::TFLs => investigator.tfl_bundle().map(|bundle| bundle.name),
+        _ => None,
+    };
+    let mut groups = vec![];
+    for directory in investigator.directories(kind) {
+        if !directory.path.exists() {
+            continue;
+        }
+        let snapshot = investigator.snapshot(&directory.path);
+        for file_type in directory.file_types.iter() {
+            let formats = investigator.settings().formats(file_type);
+            let expected = candidates
+                .iter()
+                .chain(aliases.iter())
+                .flat_map(|item| {
+                    formats.iter().map(|format| {
+                        normalize(&filename_as(item, &directory.group, file_type, format))
+                    })
+                })
+                .collect::<HashSet<_>>();
+            let suffixes = formats
+                .iter()
+                .map(|format| format!(".{}", format))
+                .collect::<Vec<_>>();
+            let files = snapshot
+                .names()
+                .filter_map(|name| {
+                    let suffix = suffixes
+                        .iter()
+                        .find(|suffix| name.to_lowercase().ends_with(suffix.as_str()))?;
+                    let orphan =
+                        !expected.contains(&normalize(name)) && bundle.as_deref().ne(&Some(name));
+                    orphan.then(|| OrphanFile {
+                        name: name.to_string(),
+                        suggestion: closest(&base_name(name, suffix), &candidates),
+                        modified_at: snapshot.file(name).map(|f| f.modified_at),
+                    })
+                })
+                .collect::<Vec<_>>();
+            if files.is_empty() {
+                continue;
+            }
+            groups.push(OrphanGroup {
+                directory: directory.path.to_string_lossy().to_string(),
+                group: directory.group,
+                file_type: *file_type,
+                files,
+            });
+        }
+    }
+    Ok(groups)
+}
+
+/// names of all items could be found in directories, supp domains are included for sdtm
+fn candidates(config: &[Config], kind: &Kind) -> Vec<String> {
+    let mut candidates = vec![];
+    for item in config.iter() {
+        candidates.push(item.name.to_lowercase());
+        if let Kind::SDTM = kind {
+            candidates.push(format!("supp{}", item.name.to_lowercase()));
+        }
+    }
+    candidates
+}
+
+/// strip extention and validation prefix, eg. `v-t-14-01-01.rtf` => `t-14-01-01`
+fn base_name(name: &str, suffix: &str) -> String {
+    let name = name.to_lowercase();
+    let name = name.strip_suffix(suffix).unwrap_or(&name);
+    VALIDATION_PREFIXES
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .unwrap_or(name)
+        .to_string()
+}
+
+/// nearest candidate to name, none if even the nearest needs more edits than two thirds of the name
+fn closest(name: &str, candidates: &[String]) -> Option<String> {
+    let name = normalize(name);
+    let limit = name.chars().count() * 2 / 3;
+    candidates
+        .iter()
+        .map(|candidate| (distance(&name, &normalize(candidate)), candidate))
+        .min_by_key(|(distance, _)| *distance)
+        .filter(|(distance, _)| *distance <= limit)
+        .map(|(_, candidate)| candidate.clone())
+}
+
+/// levenshtein distance between two names
+fn distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, x) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, y) in b.iter().enumerate() {
+            let cost = if x.eq(y) { 0 } else { 1 };
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_xlsxwriter::Workbook;
+    use std::fs;
+
+    #[test]
+    fn test_orphan_detail() -> anyhow::Result<()> {
+        let root = std::env::temp_dir().join("inspector_orphan_test");
+        fs::remove_dir_all(&root).ok();
+        let param = InvestigatorParam {
+            product: "ak112".into(),
+            trial: "303".into(),
+            purpose: "CSR".into(),
+            root: root.as_path(),
+        };
+        fs::create_dir_all(&root)?;
+        let top = root.join("top.xlsx");
+        let mut workbook = Workbook::new();
+        let sheet = workbook.add_worksheet().set_name("top")?;
+        sheet.write_string(0, 0, "Validation Level")?;
+        sheet.write_string(0, 4, "Output Name")?;
+        sheet.write_string(1, 4, "t-14-01-01")?;
+        sheet.write_string(2, 4, "t-14-01-02")?;
+        workbook.save(&top)?;
+        let directories = Investigator::new(&param).tfl_directories();
+        let (program, output) = (&directories[0].path, &directories[2].path);
+        fs::create_dir_all(program)?;
+        fs::create_dir_all(output)?;
+        for name in [
+            "t-14-01-01.sas",
+            "t_14_01_02.log",
+            "t-14-01-01_old.sas",
+            "summary.sas",
+        ] {
+            fs::write(program.join(name), "")?;
+        }
+        for name in [
+            "t-14-01-01.rtf",
+            "t-14-01-02-v2.rtf",
+            "AK112-303 All TFLs.rtf",
+        ] {
+            fs::write(output.join(name), "")?;
+        }
+
+        let groups = orphan_detail(&param, top.as_path(), &Kind::TFLs)?;
+        let found = groups
+            .iter()
+            .map(|group| {
+                let files = group
+                    .files
+                    .iter()
+                    .map(|file| (file.name.as_str(), file.suggestion.as_deref()))
+                    .collect::<Vec<_>>();
+                (group.file_type, files)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    FileType::Code,
+                    vec![
+                        ("summary.sas", None),
+                        ("t-14-01-01_old.sas", Some("t-14-01-01")),
+                    ]
+                ),
+                // the bundle of all outputs is not an orphan
+                (
+                    FileType::Output,
+                    vec![("t-14-01-02-v2.rtf", Some("t-14-01-02"))]
+                ),
+            ]
+        );
+        assert_eq!(groups[0].directory, program.to_string_lossy());
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_closest() {
+        let candidates = vec!["ae".to_string(), "t-14-01-01".into(), "t-14-02-01".into()];
+        assert_eq!(
+            closest(&base_name("v-t_14_01_01.rtf", ".rtf"), &candidates),
+            Some("t-14-01-01".into())
+        );
+        assert_eq!(
+            closest(&base_name("ae_old.sas", ".sas"), &candidates),
+            Some("ae".into())
+        );
+        assert_eq!(
+            closest(&base_name("summary.sas", ".sas"), &candidates),
+            None
+        );
+        assert_eq!(distance("ae", "ae_old"), 4);
+    }
+}
@@ -0,0 +1,342 @@
+use super::{
+    category::Group,
+    config::reader::Config,
+    error::Error,
+    inspect::result::{LintFinding, LintRule, OutputLintResult, Status},
+    investigator::{investigator::Investigator, utils::normalize},
+    rtf::{self, RtfText},
+};
+use chrono::NaiveDate;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::path::PathBuf;
+
+lazy_static! {
+    static ref NO_DATA: Regex = Regex::new(r"(?i)\bno (?:data|observations?)\b").unwrap();
+    /// values never filled, eg. `XX.X`, macro variables never resolved, eg. `&title`, and sas errors
+    static ref PLACEHOLDERS: [Regex; 3] = [
+        Regex::new(r"\b(?:X+\.X+|XX+)\b").unwrap(),
+        Regex::new(r"(?:^|[^\w&])(&[A-Za-z_]\w*)").unwrap(),
+        Regex::new(r"\bERROR\b").unwrap(),
+    ];
+    static ref PROGRAM: Regex = Regex::new(r"(?i)([\w-]+)\.sas\b").unwrap();
+    /// label of the date output is produced on, eg. `Output date: 05MAR2024`
+    static ref RUN_DATE: Regex =
+        Regex::new(r"(?i)\b(?:output|run|produced|creat(?:ed|ion)|generated)\s*(?:date|on)\b").unwrap();
+    static ref ISO_DATE: Regex = Regex::new(r"\b(\d{4}-\d{2}-\d{2})\b").unwrap();
+    /// date9 format of sas, eg. `01MAR2024`
+    static ref SAS_DATE: Regex = Regex::new(r"(?i)\b(\d{2}[a-z]{3}\d{4})\b").unwrap();
+}
+
+/// structural findings of rtf output of item, `alias` is the base name its files are saved as
+pub(crate) fn lint(
+    item: &str,
+    alias: Option<&str>,
+    text: &RtfText,
+    data_cut: Option<NaiveDate>,
+) -> OutputLintResult {
+    let mut findings = vec![];
+    if text.body.is_empty() {
+        findings.push(finding(LintRule::NoData, "Output body is empty".into()));
+    } else if let Some(line) = text.body.iter().find(|line| NO_DATA.is_match(line)) {
+        findings.push(finding(
+            LintRule::NoData,
+            format!("Output reports no data: {}", line),
+        ));
+    }
+    findings.extend(placeholders(text));
+    findings.extend(programs(item, alias, text));
+    if let Some(data_cut) = data_cut {
+        if let Some(date) = run_date(text).filter(|date| date.lt(&data_cut)) {
+            findings.push(finding(
+                LintRule::OutdatedOutput,
+                format!("Output dated {} is before data cut {}", date, data_cut),
+            ));
+        }
+    }
+    let status = match findings.is_empty() {
+        true => Status::Pass,
+        false => Status::Failed(format!("{} lint findings", findings.len())),
+    };
+    OutputLintResult {
+        status,
+        pages: text.pages,
+        findings,
+    }
+}
+
+/// rtf in production output directory which no item of top is saved as
+#[derive(Debug, Serialize)]
+pub struct UnlistedOutput {
+    pub name: String,
+    pub path: PathBuf,
+    pub lint: OutputLintResult,
+}
+
+/// lint every rtf in production output directory that is not the output of an item of top,
+/// the bundle of all outputs aside, so outputs dropped from top are reported instead of skipped
+pub(crate) fn lint_unlisted(investigator: &Investigator, config: &[Config]) -> Vec<UnlistedOutput> {
+    let listed = config
+        .iter()
+        .filter_map(|item| investigator.tfl_output(&item.name, &Group::Production))
+        .map(|file| file.name)
+        .chain(investigator.tfl_bundle().map(|file| file.name))
+        .collect::<Vec<_>>();
+    let settings = investigator.settings();
+    investigator
+        .tfl_rtf_outputs()
+        .into_iter()
+        .filter(|file| !listed.contains(&file.name))
+        .map(|file| {
+            let item = file
+                .filepath
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| file.name.clone());
+            let not_in_top = finding(
+                LintRule::NotInTop,
+                format!("Output {} is not listed in TOP", file.name),
+            );
+            let lint = match rtf::read_text(&file.filepath) {
+                Ok(text) => {
+                    let mut lint = lint(&item, None, &text, settings.data_cut);
+                    lint.findings.insert(0, not_in_top);
+                    lint.status = Status::Failed(format!("{} lint findings", lint.findings.len()));
+                    lint
+                }
+                Err(source) => {
+                    let error = Error::ReadOutputFailed {
+                        name: item,
+                        path: file.filepath.clone(),
+                        source,
+                    };
+                    Status::Error(error.to_string()).into()
+                }
+            };
+            UnlistedOutput {
+                name: file.name,
+                path: file.filepath,
+                lint,
+            }
+        })
+        .collect()
+}
+
+fn finding(rule: LintRule, message: String) -> LintFinding {
+    LintFinding { rule, message }
+}
+
+/// date output is produced on, taken from the footnote or title labelling it, or else the line
+/// naming the program, other dates such as the data cut or visit windows are not run dates
+fn run_date(text: &RtfText) -> Option<NaiveDate> {
+    let lines = || text.footnotes.iter().chain(text.titles.iter());
+    lines()
+        .filter(|line| RUN_DATE.is_match(line))
+        .chain(lines().filter(|line| PROGRAM.is_match(line)))
+        .find_map(|line| dates(line).into_iter().max())
+}
+
+/// first line each placeholder is found in, listings may repeat it on every row
+fn placeholders(text: &RtfText) -> Vec<LintFinding> {
+    let mut found: Vec<String> = vec![];
+    let mut findings = vec![];
+    let lines = text
+        .titles
+        .iter()
+        .chain(text.body.iter())
+        .chain(text.footnotes.iter());
+    for line in lines {
+        for pattern in PLACEHOLDERS.iter() {
+            for captures in pattern.captures_iter(line) {
+                let placeholder = captures
+                    .get(1)
+                    .or_else(|| captures.get(0))
+                    .map_or("", |m| m.as_str())
+                    .to_string();
+                if found.contains(&placeholder) {
+                    continue;
+                }
+                findings.push(finding(
+                    LintRule::Placeholder,
+                    format!("Placeholder {} found: {}", placeholder, line),
+                ));
+                found.push(placeholder);
+            }
+        }
+    }
+    findings
+}
+
+/// programs named in header and footer must agree with each other and with item
+fn programs(item: &str, alias: Option<&str>, text: &RtfText) -> Vec<LintFinding> {
+    let names = |lines: &[String]| {
+        let mut names: Vec<String> = vec![];
+        for captures in lines.iter().flat_map(|line| PROGRAM.captures_iter(line)) {
+            let name = captures[1].to_string();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    };
+    let header = names(&text.titles);
+    let footer = names(&text.footnotes);
+    let mut findings = vec![];
+    if let (Some(header), Some(footer)) = (header.first(), footer.first()) {
+        if normalize(header).ne(&normalize(footer)) {
+            findings.push(finding(
+                LintRule::ProgramMismatch,
+                format!(
+                    "Header names program {}.sas but footer names {}.sas",
+                    header, footer
+                ),
+            ));
+        }
+    }
+    let expected = [Some(item), alias]
+        .into_iter()
+        .flatten()
+        .map(normalize)
+        .collect::<Vec<_>>();
+    for name in header.iter().chain(footer.iter()) {
+        if !expected.contains(&normalize(name)) {
+            findings.push(finding(
+                LintRule::ProgramMismatch,
+                format!("Program {}.sas is not the program of {}", name, item),
+            ));
+            break;
+        }
+    }
+    findings
+}
+
+fn dates(line: &str) -> Vec<NaiveDate> {
+    let iso = ISO_DATE
+        .captures_iter(line)
+        .filter_map(|captures| NaiveDate::parse_from_str(&captures[1], "%Y-%m-%d").ok());
+    let sas = SAS_DATE
+        .captures_iter(line)
+        .filter_map(|captures| NaiveDate::parse_from_str(&captures[1], "%d%b%Y").ok());
+    iso.chain(sas).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::investigator::investigator::InvestigatorParam;
+    use std::fs;
+
+    fn text(body: &[&str], footnotes: &[&str]) -> RtfText {
+        RtfText {
+            titles: vec![
+                "Table 14.1.1 Summary of Demographics".into(),
+                "Program: t-14-01-01.sas".into(),
+            ],
+            footnotes: footnotes.iter().map(|line| line.to_string()).collect(),
+            body: body.iter().map(|line| line.to_string()).collect(),
+            pages: 3,
+        }
+    }
+
+    #[test]
+    fn test_lint() {
+        let data_cut = NaiveDate::from_ymd_opt(2024, 3, 1);
+        let passed = lint(
+            "t-14-01-01",
+            None,
+            &text(
+                &["Age (years)", "Mean (SD)", "R&D site"],
+                &["Source: t_14_01_01.sas  Output date: 05MAR2024"],
+            ),
+            data_cut,
+        );
+        assert_eq!(passed.status, Status::Pass);
+        assert_eq!(passed.pages, 3);
+
+        let failed = lint(
+            "t-14-01-01",
+            None,
+            &text(
+                &["No data to report", "XX.X (XX.X)", "XX.X (XX.X)", "&trt1"],
+                &["Source: t-14-01-02.sas  Output date: 2024-02-28"],
+            ),
+            data_cut,
+        );
+        let rules = failed
+            .findings
+            .iter()
+            .map(|finding| finding.rule)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rules,
+            vec![
+                LintRule::NoData,
+                LintRule::Placeholder,
+                LintRule::Placeholder,
+                LintRule::ProgramMismatch,
+                LintRule::ProgramMismatch,
+                LintRule::OutdatedOutput,
+            ]
+        );
+        assert_eq!(failed.findings[2].message, "Placeholder &trt1 found: &trt1");
+        assert_eq!(failed.status, Status::Failed("6 lint findings".into()));
+        let empty = lint("t-14-01-01", None, &text(&[], &[]), None);
+        assert_eq!(empty.findings[0].message, "Output body is empty");
+
+        // only the run date counts, not dates of data cut or periods in other footnotes
+        let dated = lint(
+            "t-14-01-01",
+            None,
+            &text(
+                &["Age (years)"],
+                &[
+                    "Data cut-off: 2024-01-15, period 2023-01-01 to 2024-02-29",
+                    "Source: t-14-01-01.sas  Output date: 2024-02-28",
+                ],
+            ),
+            data_cut,
+        );
+        assert_eq!(
+            dated.findings[0].message,
+            "Output dated 2024-02-28 is before data cut 2024-03-01"
+        );
+        let undated = text(&["Age (years)"], &["Period 2024-01-01 to 2024-02-29"]);
+        assert_eq!(run_date(&undated), None);
+    }
+
+    #[test]
+    fn test_lint_unlisted() -> anyhow::Result<()> {
+        let root = std::env::temp_dir().join("inspector_lint_unlisted_test");
+        fs::remove_dir_all(&root).ok();
+        let investigator = Investigator::new(&InvestigatorParam {
+            product: "ak112".into(),
+            trial: "303".into(),
+            purpose: "CSR".into(),
+            root: root.as_path(),
+        });
+        let output = investigator
+            .root()
+            .join(Group::Production.group_dir())
+            .join("output");
+        fs::create_dir_all(&output)?;
+        let rtf = r"{\rtf1\ansi{\header\pard{Table 14.2.1\par}}{\pard Age\par}}";
+        fs::write(output.join("t-14-01-01.rtf"), rtf)?;
+        fs::write(output.join("t-14-02-01.rtf"), rtf)?;
+        fs::write(output.join("notes.txt"), "")?;
+        let config = vec![Config {
+            name: "t-14-01-01".into(),
+            ..Default::default()
+        }];
+        let unlisted = lint_unlisted(&investigator, &config);
+        assert_eq!(unlisted.len(), 1);
+        assert_eq!(unlisted[0].name, "t-14-02-01.rtf");
+        assert_eq!(unlisted[0].lint.findings[0].rule, LintRule::NotInTop);
+        assert_eq!(
+            unlisted[0].lint.status,
+            Status::Failed("1 lint findings".into())
+        );
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+}
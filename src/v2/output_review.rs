@@ -0,0 +1,165 @@
+use super::{
+    config::{reader::Config, title::OutputTitle},
+    inspect::result::{OutputReviewResult, Status, TitleField, TitleMismatch},
+    rtf::RtfText,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    static ref OUTPUT_NUMBER: Regex =
+        Regex::new(r"(?i)^(?:table|listing|figure)\s+(\d+(?:\.\d+)*)[\s:.]*").unwrap();
+    static ref BARE_NUMBER: Regex = Regex::new(r"^\d+(?:\.\d+)*$").unwrap();
+}
+
+/// compare titles and footnotes of the rtf output with top, skip the check when top lists none
+pub(crate) fn check(config: &Config, text: Option<&RtfText>) -> Option<OutputReviewResult> {
+    let expected = config.output.as_ref().filter(|output| !output.is_empty())?;
+    Some(match text {
+        Some(text) => review(expected, text),
+        None => Status::Missing.into(),
+    })
+}
+
+/// every line of top is expected in output, titles and footnotes written into body
+/// by `bodytitle` are searched in body
+pub(crate) fn review(expected: &OutputTitle, text: &RtfText) -> OutputReviewResult {
+    let titles = match text.titles.is_empty() {
+        true => &text.body,
+        false => &text.titles,
+    };
+    let footnotes = match text.footnotes.is_empty() {
+        true => &text.body,
+        false => &text.footnotes,
+    };
+    let mut mismatches = vec![];
+    if let Some(number) = expected.number.as_deref().and_then(output_number) {
+        let actual = titles.iter().find_map(|line| output_number(line));
+        if actual.as_deref().ne(&Some(number.as_str())) {
+            mismatches.push(TitleMismatch {
+                field: TitleField::Number,
+                top: number,
+                output: actual,
+            });
+        }
+    }
+    for title in expected.titles.iter().map(|title| normalize(title)) {
+        let found = titles
+            .iter()
+            .any(|line| line.eq(&title) || strip_number(line).eq(&title));
+        if !found {
+            mismatches.push(TitleMismatch {
+                field: TitleField::Title,
+                top: title,
+                output: None,
+            });
+        }
+    }
+    if let Some(population) = expected.population.as_deref().map(normalize) {
+        if !titles.iter().any(|line| line.contains(&population)) {
+            mismatches.push(TitleMismatch {
+                field: TitleField::Population,
+                top: population,
+                output: None,
+            });
+        }
+    }
+    for footnote in expected
+        .footnotes
+        .iter()
+        .map(|footnote| normalize(footnote))
+    {
+        if !footnotes.iter().any(|line| line.eq(&footnote)) {
+            mismatches.push(TitleMismatch {
+                field: TitleField::Footnote,
+                top: footnote,
+                output: None,
+            });
+        }
+    }
+    let status = match mismatches.is_empty() {
+        true => Status::Pass,
+        false => Status::Failed(format!("{} lines differ from TOP", mismatches.len())),
+    };
+    OutputReviewResult { status, mismatches }
+}
+
+/// table number of line, eg. `14.1.1` of `Table 14.1.1 Summary of Demographics`
+pub(crate) fn output_number(line: &str) -> Option<String> {
+    let line = line.trim();
+    if BARE_NUMBER.is_match(line) {
+        return Some(line.into());
+    }
+    title_number(line)
+}
+
+/// table number of title led by output type, a bare number is not taken as it may be a page number
+pub(crate) fn title_number(line: &str) -> Option<String> {
+    OUTPUT_NUMBER
+        .captures(line.trim())
+        .map(|captures| captures[1].to_string())
+}
+
+fn strip_number(line: &str) -> &str {
+    match OUTPUT_NUMBER.find(line) {
+        Some(found) => &line[found.end()..],
+        None => line,
+    }
+}
+
+/// lines of rtf have whitespace collapsed, so do lines of top
+fn normalize(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_review() {
+        let expected = OutputTitle {
+            number: Some("Table 14.1.1".into()),
+            titles: vec!["Summary of  Demographics".into()],
+            population: Some("Safety Set".into()),
+            footnotes: vec!["Note: Percentages are based on N.".into()],
+        };
+        let text = RtfText {
+            titles: vec![
+                "Protocol: AK112-303".into(),
+                "Table 14.1.1: Summary of Demographics".into(),
+                "Safety Set".into(),
+            ],
+            footnotes: vec!["Note: Percentages are based on N.".into()],
+            body: vec![],
+            pages: 1,
+        };
+        assert_eq!(review(&expected, &text).status, Status::Pass);
+
+        let text = RtfText {
+            titles: vec![],
+            footnotes: vec![],
+            body: vec![
+                "Table 14.1.2 Summary of Demographic".into(),
+                "Full Analysis Set".into(),
+                "Note: Percentages are based on N.".into(),
+            ],
+            pages: 1,
+        };
+        let result = review(&expected, &text);
+        assert_eq!(
+            result.status,
+            Status::Failed("3 lines differ from TOP".into())
+        );
+        assert_eq!(
+            result.mismatches[0],
+            TitleMismatch {
+                field: TitleField::Number,
+                top: "14.1.1".into(),
+                output: Some("14.1.2".into()),
+            }
+        );
+        assert_eq!(result.mismatches[1].top, "Summary of Demographics");
+        assert_eq!(result.mismatches[2].field, TitleField::Population);
+    }
+}
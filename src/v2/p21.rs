@@ -0,0 +1,298 @@
+use super::{
+    category::Kind,
+    error::{Error, Result},
+    inspect::result::{InspectionResult, P21Counts, P21Result, Status},
+    investigator::investigator::{File, Investigator},
+};
+use calamine::{open_workbook, Data, DataType, Range, Reader, Xlsx};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+pub const ISSUE_SUMMARY: &str = "Issue Summary";
+pub const DETAILS: &str = "Details";
+const SUPP_PREFIX: &str = "SUPP";
+/// rows searched for the header, reports put a few title rows above it
+const HEADER_ROWS: usize = 10;
+const SOURCE_HEADERS: [&str; 2] = ["Source", "Dataset"];
+const RULE_HEADERS: [&str; 3] = ["Pinnacle 21 ID", "Rule ID", "FDA Rule ID"];
+const MESSAGE_HEADER: &str = "Message";
+const SEVERITY_HEADER: &str = "Severity";
+/// number of records an issue is found in, `Found` in issue summary and `Count` in details
+const COUNT_HEADERS: [&str; 2] = ["Found", "Count"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Reject,
+    Error,
+    Warning,
+    Notice,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct P21Issue {
+    pub dataset: String,
+    pub rule: String,
+    pub message: String,
+    pub severity: Severity,
+    pub count: usize,
+}
+
+/// read issues of a pinnacle 21 community report, issue summary is preferred as it
+/// counts records already, details is read when summary is absent
+pub fn read_p21_report<P: AsRef<Path>>(file: P) -> Result<Vec<P21Issue>> {
+    let path = file.as_ref();
+    let mut workbook: Xlsx<_> = open_workbook(path)?;
+    let sheets = workbook.sheet_names();
+    for target in [ISSUE_SUMMARY, DETAILS] {
+        let Some(name) = sheets.iter().find(|name| name.eq_ignore_ascii_case(target)) else {
+            continue;
+        };
+        let range = workbook.worksheet_range(name)?;
+        return issues(&range).map_err(|cause| Error::ReadP21Failed {
+            path: path.to_path_buf(),
+            source: cause.into(),
+        });
+    }
+    Err(Error::ReadP21Failed {
+        path: path.to_path_buf(),
+        source: format!("neither {} nor {} sheet found", ISSUE_SUMMARY, DETAILS).into(),
+    })
+}
+
+/// count issues of the latest report of kind into results, and check the report
+/// is newer than the xpt files of each item, a report that can not be read is an
+/// error status of every result
+pub fn attach_p21(investigator: &Investigator, kind: &Kind, results: &mut [InspectionResult]) {
+    let Some(report) = investigator.p21_report(kind) else {
+        return;
+    };
+    let issues = read_p21_report(&report.filepath).map_err(|e| e.to_string());
+    for result in results.iter_mut() {
+        let xpts = match kind {
+            Kind::SDTM => vec![
+                investigator.sdtm_xpt_main(&result.item),
+                investigator.sdtm_xpt_supp(&result.item),
+            ],
+            Kind::ADaM => vec![investigator.adam_xpt(&result.item)],
+            Kind::TFLs => return,
+        };
+        let xpts = xpts.into_iter().flatten().collect::<Vec<_>>();
+        let (counts, status) = match issues.as_ref() {
+            Ok(issues) => (counts(issues, &result.item), freshness(&report, &xpts)),
+            Err(e) => (P21Counts::default(), Status::Error(e.clone())),
+        };
+        result.p21 = Some(P21Result {
+            report: report.filepath.clone(),
+            report_time: report.modified_at,
+            counts,
+            status,
+        });
+    }
+}
+
+/// issues of item, including those of its supp domain
+fn counts(issues: &[P21Issue], item: &str) -> P21Counts {
+    let supp = format!("{}{}", SUPP_PREFIX, item);
+    let mut counts = P21Counts::default();
+    for issue in issues.iter().filter(|issue| {
+        issue.dataset.eq_ignore_ascii_case(item) || issue.dataset.eq_ignore_ascii_case(&supp)
+    }) {
+        match issue.severity {
+            Severity::Reject => counts.reject += issue.count,
+            Severity::Error => counts.error += issue.count,
+            Severity::Warning => counts.warning += issue.count,
+            Severity::Notice => counts.notice += issue.count,
+        }
+    }
+    counts
+}
+
+/// report validated the xpt files only if it is modified after all of them
+fn freshness(report: &File<PathBuf>, xpts: &[File<PathBuf>]) -> Status {
+    if xpts.is_empty() {
+        return Status::Missing;
+    }
+    let latest = xpts
+        .iter()
+        .filter(|xpt| xpt.modified_at.gt(&report.modified_at))
+        .max_by_key(|xpt| xpt.modified_at);
+    match latest {
+        Some(xpt) => Status::Failed(format!(
+            "P21 report {} is older than {}",
+            report.name, xpt.name
+        )),
+        None => Status::Pass,
+    }
+}
+
+fn issues(range: &Range<Data>) -> std::result::Result<Vec<P21Issue>, String> {
+    let rows = range.rows().collect::<Vec<_>>();
+    let Some(header_row) = rows
+        .iter()
+        .take(HEADER_ROWS)
+        .position(|row| column(row, &[SEVERITY_HEADER]).is_some())
+    else {
+        return Err(format!("{} column not found", SEVERITY_HEADER));
+    };
+    let header = rows[header_row];
+    let source_column = column(header, &SOURCE_HEADERS).ok_or("Source column not found")?;
+    let severity_column = column(header, &[SEVERITY_HEADER]);
+    let rule_column = column(header, &RULE_HEADERS);
+    let message_column = column(header, &[MESSAGE_HEADER]);
+    let count_column = column(header, &COUNT_HEADERS);
+    let mut issues = vec![];
+    // summary only names the dataset on the first row of its issues
+    let mut dataset = String::new();
+    for row in rows.iter().skip(header_row + 1) {
+        let source = text(row, Some(source_column));
+        if !source.is_empty() {
+            dataset = source;
+        }
+        let severity = match text(row, severity_column).to_lowercase().as_str() {
+            "reject" => Severity::Reject,
+            "error" => Severity::Error,
+            "warning" => Severity::Warning,
+            "notice" | "info" => Severity::Notice,
+            _ => continue,
+        };
+        let count = count_column
+            .and_then(|col| row.get(col))
+            .and_then(|cell| cell.as_f64())
+            .map_or(1, |count| count as usize);
+        issues.push(P21Issue {
+            dataset: dataset.clone(),
+            rule: text(row, rule_column),
+            message: text(row, message_column),
+            severity,
+            count,
+        });
+    }
+    Ok(issues)
+}
+
+fn column(row: &[Data], headers: &[&str]) -> Option<usize> {
+    row.iter().position(|cell| {
+        let cell = cell.as_string().unwrap_or_default();
+        headers
+            .iter()
+            .any(|header| cell.trim().eq_ignore_ascii_case(header))
+    })
+}
+
+fn text(row: &[Data], col: Option<usize>) -> String {
+    col.and_then(|col| row.get(col))
+        .and_then(|cell| cell.as_string())
+        .map(|cell| cell.trim().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::investigator::investigator::InvestigatorParam;
+    use chrono::{Local, TimeZone};
+    use rust_xlsxwriter::Workbook;
+    use std::fs;
+
+    #[test]
+    fn test_read_p21_report() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join("inspector_p21_test");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir)?;
+        let file = dir.join("ak112-303-sdtm-p21.xlsx");
+        let mut workbook = Workbook::new();
+        workbook.add_worksheet().set_name("Dataset Summary")?;
+        let sheet = workbook.add_worksheet().set_name(ISSUE_SUMMARY)?;
+        let header = ["Source", "Pinnacle 21 ID", "Message", "Severity", "Found"];
+        for (col, name) in header.iter().enumerate() {
+            sheet.write_string(1, col as u16, *name)?;
+        }
+        let rows = [
+            (
+                "AE",
+                "SD0002",
+                "NULL value in variable marked as Required",
+                "Error",
+                3,
+            ),
+            ("", "SD1082", "Variable length is too long", "Warning", 2),
+            ("SUPPAE", "SD0083", "Duplicate records", "Reject", 1),
+            ("DM", "SD0087", "RFSTDTC is not populated", "Warning", 5),
+        ];
+        for (index, (source, rule, message, severity, found)) in rows.iter().enumerate() {
+            let row = index as u32 + 2;
+            sheet.write_string(row, 0, *source)?;
+            sheet.write_string(row, 1, *rule)?;
+            sheet.write_string(row, 2, *message)?;
+            sheet.write_string(row, 3, *severity)?;
+            sheet.write_number(row, 4, *found)?;
+        }
+        workbook.save(&file)?;
+
+        let issues = read_p21_report(&file)?;
+        assert_eq!(issues.len(), 4);
+        assert_eq!(issues[1].dataset, "AE");
+        assert_eq!(issues[1].rule, "SD1082");
+        let counts = counts(&issues, "ae");
+        assert_eq!(
+            counts,
+            P21Counts {
+                reject: 1,
+                error: 3,
+                warning: 2,
+                notice: 0
+            }
+        );
+
+        let time = |hour: u32| Local.with_ymd_and_hms(2024, 3, 1, hour, 0, 0).unwrap();
+        let file = |name: &str, hour: u32| File {
+            name: name.into(),
+            filepath: PathBuf::from(name),
+            created_at: time(hour),
+            modified_at: time(hour),
+        };
+        let report = file("ak112-303-sdtm-p21.xlsx", 10);
+        assert_eq!(freshness(&report, &[file("ae.xpt", 9)]), Status::Pass);
+        assert_eq!(
+            freshness(&report, &[file("ae.xpt", 9), file("suppae.xpt", 11)]),
+            Status::Failed("P21 report ak112-303-sdtm-p21.xlsx is older than suppae.xpt".into())
+        );
+        assert_eq!(freshness(&report, &[]), Status::Missing);
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_p21_report() -> anyhow::Result<()> {
+        let root = std::env::temp_dir().join("inspector_p21_report_test");
+        fs::remove_dir_all(&root).ok();
+        let investigator = Investigator::new(&InvestigatorParam {
+            product: "ak112".into(),
+            trial: "303".into(),
+            purpose: "CSR".into(),
+            root: root.as_path(),
+        });
+        let dir = investigator.p21_dir();
+        fs::create_dir_all(&dir)?;
+        for name in [
+            "ak112-303-sdtm-p21.xlsx",
+            "ak112-303-sdtm-p21.csv",
+            "~$ak112-303-sdtm-p21.xlsx",
+        ] {
+            fs::write(dir.join(name), "")?;
+        }
+        let report = investigator.p21_report(&Kind::SDTM).unwrap();
+        assert_eq!(report.name, "ak112-303-sdtm-p21.xlsx");
+        assert!(investigator.p21_report(&Kind::ADaM).is_none());
+        let mut results = vec![InspectionResult {
+            item: "ae".into(),
+            ..Default::default()
+        }];
+        attach_p21(&investigator, &Kind::SDTM, &mut results);
+        let p21 = results[0].p21.as_ref().unwrap();
+        assert!(matches!(p21.status, Status::Error(_)));
+        assert_eq!(p21.counts, P21Counts::default());
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,397 @@
+use super::{
+    category::{Group, Kind},
+    error::{Error, Result},
+    inspect::{
+        inspector::{item_log, item_sequences},
+        result::{InspectionResult, LogResult, Status},
+    },
+    investigator::investigator::{Investigator, InvestigatorParam},
+    sequence::audit::AuditResult,
+};
+use chrono::Local;
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub const REPORT_FILE: &str = "inspection-report.html";
+
+const STYLE: &str = r#"
+body { font-family: "Segoe UI", Arial, sans-serif; margin: 24px; color: #222; }
+h1 { font-size: 22px; } h2 { font-size: 18px; margin-top: 32px; }
+.filters { position: sticky; top: 0; background: #fff; padding: 8px 0; }
+.filters input, .filters select { margin-right: 12px; padding: 4px; }
+table { border-collapse: collapse; width: 100%; font-size: 13px; }
+th, td { border: 1px solid #ddd; padding: 4px 8px; text-align: left; vertical-align: top; }
+th { background: #f3f3f3; }
+td.pass { background: #d8f0d8; } td.failed { background: #f8d4d4; } td.error { background: #f3b8b8; }
+td.missing { background: #eee; color: #777; } td.notstart { background: #fff4cc; }
+tr.detail td { background: #fafafa; }
+details summary { cursor: pointer; color: #1a5fb4; }
+pre { white-space: pre-wrap; margin: 2px 0; }
+.fail-row { color: #b00; }
+"#;
+
+const SCRIPT: &str = r#"
+function filterRows() {
+  var text = document.getElementById('filter-item').value.toLowerCase();
+  var state = document.getElementById('filter-state').value;
+  document.querySelectorAll('tbody.item').forEach(function (item) {
+    var visible = item.dataset.name.indexOf(text) >= 0 && (state === '' || item.dataset.state === state);
+    item.style.display = visible ? '' : 'none';
+  });
+}
+"#;
+
+/// an inspected item with the details shown when drilling down
+pub struct ReportItem {
+    pub result: InspectionResult,
+    pub logs: Vec<(Group, LogResult)>,
+    pub sequences: Vec<AuditResult>,
+}
+
+pub struct ReportSection {
+    pub kind: Kind,
+    pub items: Vec<ReportItem>,
+}
+
+/// collect log and sequence details of inspection results, the investigator of the study
+/// is shared by every item of the section
+pub fn report_section(
+    investigator: &Investigator,
+    kind: &Kind,
+    results: Vec<InspectionResult>,
+) -> ReportSection {
+    let mut items = Vec::with_capacity(results.len());
+    for result in results {
+        let mut logs = vec![];
+        for group in [Group::Production, Group::Validation] {
+            // a log that can not be read is shown on its item instead of failing the report
+            let log =
+                item_log(investigator, &result.item, kind, &group).unwrap_or_else(|e| LogResult {
+                    status: Status::Error(e.to_string()),
+                    details: vec![],
+                    evidence: None,
+                });
+            logs.push((group, log));
+        }
+        let supp = result.qc_supp.is_some();
+        let sequences = item_sequences(investigator, &result.item, supp, kind);
+        items.push(ReportItem {
+            result,
+            logs,
+            sequences,
+        });
+    }
+    ReportSection {
+        kind: kind.clone(),
+        items,
+    }
+}
+
+/// render report and save it under utility folder of the study
+pub fn save_report<P: AsRef<Path>>(
+    param: &InvestigatorParam<P>,
+    sections: &[ReportSection],
+) -> Result<PathBuf> {
+    let title = format!("{} {} {}", param.product, param.trial, param.purpose);
+    let file = Investigator::try_new(param)?.utility().join(REPORT_FILE);
+    fs::write(&file, render(&title, sections)).map_err(Error::OutputFailed)?;
+    Ok(file)
+}
+
+/// render sections into a single html file, styles and scripts are inlined
+pub fn render(title: &str, sections: &[ReportSection]) -> String {
+    let mut html = String::new();
+    writeln!(
+        html,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">"
+    )
+    .ok();
+    writeln!(html, "<title>{}</title>", escape(title)).ok();
+    writeln!(
+        html,
+        "<style>{}</style>\n<script>{}</script>",
+        STYLE, SCRIPT
+    )
+    .ok();
+    writeln!(html, "</head>\n<body>").ok();
+    writeln!(html, "<h1>{}</h1>", escape(title)).ok();
+    writeln!(
+        html,
+        "<p>Generated at {}</p>",
+        Local::now().format("%Y-%m-%d %H:%M:%S")
+    )
+    .ok();
+    html.push_str(concat!(
+        "<div class=\"filters\">",
+        "<input id=\"filter-item\" placeholder=\"Item\" oninput=\"filterRows()\">",
+        "<select id=\"filter-state\" onchange=\"filterRows()\">",
+        "<option value=\"\">All</option><option value=\"pass\">Pass</option>",
+        "<option value=\"failed\">Failed</option><option value=\"error\">Error</option>",
+        "<option value=\"missing\">Missing</option><option value=\"notstart\">Not Start</option>",
+        "</select></div>\n"
+    ));
+    for section in sections.iter() {
+        render_section(&mut html, section);
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn render_section(html: &mut String, section: &ReportSection) {
+    let kind = match section.kind {
+        Kind::SDTM => "SDTM",
+        Kind::ADaM => "ADaM",
+        Kind::TFLs => "TFLs",
+    };
+    writeln!(
+        html,
+        "<h2>{} ({} items)</h2>\n<table>",
+        kind,
+        section.items.len()
+    )
+    .ok();
+    html.push_str(concat!(
+        "<thead><tr><th>Item</th><th>QC</th><th>QC Supp</th>",
+        "<th>Production Log</th><th>Production Sequence</th>",
+        "<th>Validation Log</th><th>Validation Sequence</th><th>Conformance</th></tr></thead>\n"
+    ));
+    for item in section.items.iter() {
+        render_item(html, item);
+    }
+    html.push_str("</table>\n");
+}
+
+fn render_item(html: &mut String, item: &ReportItem) {
+    let result = &item.result;
+    let conformance = result.conformance.as_ref().map(|c| &c.status);
+    let statuses = [
+        Some(&result.qc),
+        result.qc_supp.as_ref(),
+        Some(&result.production_result.log),
+        Some(&result.production_result.sequence),
+        Some(&result.validation_result.log),
+        Some(&result.validation_result.sequence),
+        conformance,
+    ];
+    writeln!(
+        html,
+        "<tbody class=\"item\" data-name=\"{}\" data-state=\"{}\"><tr>",
+        escape(&result.item.to_lowercase()),
+        state(statuses.iter().flatten().copied())
+    )
+    .ok();
+    writeln!(html, "<td>{}</td>", escape(&result.item)).ok();
+    for status in statuses {
+        let cell = match status {
+            Some(status) => format!("<td class=\"{}\">{}</td>", class(status), status.label()),
+            None => "<td></td>".into(),
+        };
+        html.push_str(&cell);
+    }
+    html.push_str(
+        "</tr>\n<tr class=\"detail\"><td colspan=\"8\"><details><summary>Details</summary>\n",
+    );
+    render_reasons(html, result);
+    render_logs(html, &item.logs);
+    render_sequences(html, &item.sequences);
+    html.push_str("</details></td></tr></tbody>\n");
+}
+
+fn render_reasons(html: &mut String, result: &InspectionResult) {
+    for (name, status) in result.checks() {
+        if let Some(reason) = status.reason() {
+            writeln!(html, "<p><b>{}</b>: {}</p>", name, escape(reason)).ok();
+        }
+    }
+    if let Some(conformance) = result.conformance.as_ref() {
+        for variable in conformance.missing.iter() {
+            writeln!(html, "<p>Missing variable {}</p>", escape(variable)).ok();
+        }
+        for variable in conformance.extra.iter() {
+            writeln!(html, "<p>Extra variable {}</p>", escape(variable)).ok();
+        }
+        for mismatch in conformance.mismatches.iter() {
+            writeln!(
+                html,
+                "<p>{} {:?}: spec {}, data {}</p>",
+                escape(&mismatch.variable),
+                mismatch.attribute,
+                escape(&mismatch.spec),
+                escape(&mismatch.data)
+            )
+            .ok();
+        }
+    }
+    if let Some(review) = result.output_review.as_ref() {
+        for mismatch in review.mismatches.iter() {
+            writeln!(
+                html,
+                "<p>{:?} in TOP: {}, output: {}</p>",
+                mismatch.field,
+                escape(&mismatch.top),
+                escape(mismatch.output.as_deref().unwrap_or("not found"))
+            )
+            .ok();
+        }
+    }
+    if let Some(lint) = result.output_lint.as_ref() {
+        for finding in lint.findings.iter() {
+            writeln!(html, "<p>{}</p>", escape(&finding.message)).ok();
+        }
+    }
+    for evidence in result.evidence.iter() {
+        writeln!(html, "<p>{}</p>", escape(&evidence.message)).ok();
+    }
+    for fallback in result.fallbacks.iter() {
+        writeln!(
+            html,
+            "<p>{} expected, {} found by {:?} match</p>",
+            escape(&fallback.expected),
+            escape(&fallback.actual),
+            fallback.rule
+        )
+        .ok();
+    }
+}
+
+fn render_logs(html: &mut String, logs: &[(Group, LogResult)]) {
+    for (group, log) in logs.iter() {
+        if let Status::Error(reason) = &log.status {
+            writeln!(
+                html,
+                "<p><b>{:?} log</b></p>\n<pre class=\"fail-row\">{}</pre>",
+                group,
+                escape(reason)
+            )
+            .ok();
+            continue;
+        }
+        if log.details.is_empty() {
+            continue;
+        }
+        writeln!(html, "<p><b>{:?} log</b></p>", group).ok();
+        for row in log.details.iter() {
+            let class = if row.pass { "" } else { " class=\"fail-row\"" };
+            writeln!(
+                html,
+                "<pre{}>{:>6}  {}</pre>",
+                class,
+                row.row,
+                escape(&row.content)
+            )
+            .ok();
+        }
+    }
+}
+
+fn render_sequences(html: &mut String, sequences: &[AuditResult]) {
+    if sequences.is_empty() {
+        return;
+    }
+    html.push_str("<table><tr><th>Group</th><th>File</th><th>Type</th><th>Modified At</th><th>Status</th></tr>\n");
+    for sequence in sequences.iter().flat_map(|audit| audit.sequences.iter()) {
+        let modified_at = sequence
+            .modified_at
+            .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        writeln!(
+            html,
+            "<tr><td>{:?}</td><td>{}</td><td>{:?}</td><td>{}</td><td class=\"{}\">{}</td></tr>",
+            sequence.group,
+            escape(&sequence.name),
+            sequence.kind,
+            modified_at,
+            class(&sequence.status),
+            sequence.status.label()
+        )
+        .ok();
+    }
+    html.push_str("</table>\n");
+}
+
+/// overall state of item used by the filter, the worst status wins
+fn state<'a, I: Iterator<Item = &'a Status>>(statuses: I) -> &'static str {
+    match statuses.min_by_key(|status| status.severity()) {
+        Some(Status::Error(_)) => "error",
+        Some(Status::Failed(_)) => "failed",
+        Some(Status::Missing) => "missing",
+        Some(Status::NotStart) => "notstart",
+        Some(Status::Pass) | None => "pass",
+    }
+}
+
+fn class(status: &Status) -> &'static str {
+    state(std::iter::once(status))
+}
+
+fn escape(content: &str) -> String {
+    content
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::inspect::result::{IndividualResult, LogRow};
+
+    #[test]
+    fn test_render() {
+        let result = InspectionResult {
+            item: "t-14-01-01".into(),
+            qc: Status::Failed("Value <N> differs".into()),
+            production_result: IndividualResult {
+                start_coding: true,
+                log: Status::Pass,
+                sequence: Status::Pass,
+            },
+            validation_result: IndividualResult {
+                start_coding: true,
+                log: Status::Failed("ERROR found".into()),
+                sequence: Status::Missing,
+            },
+            ..Default::default()
+        };
+        let logs = vec![
+            (
+                Group::Validation,
+                LogResult {
+                    status: Status::Failed("ERROR found".into()),
+                    details: vec![LogRow {
+                        row: 12,
+                        content: "ERROR: Variable AVAL not found.".into(),
+                        pass: false,
+                    }],
+                    evidence: None,
+                },
+            ),
+            (
+                Group::Production,
+                LogResult {
+                    status: Status::Error("Failed to read log t-14-01-01.log".into()),
+                    details: vec![],
+                    evidence: None,
+                },
+            ),
+        ];
+        let sections = vec![ReportSection {
+            kind: Kind::TFLs,
+            items: vec![ReportItem {
+                result,
+                logs,
+                sequences: vec![],
+            }],
+        }];
+        let html = render("ak112 303 CSR", &sections);
+        assert!(html.contains("data-state=\"failed\""));
+        assert!(html.contains("Value &lt;N&gt; differs"));
+        assert!(html.contains("ERROR: Variable AVAL not found."));
+        assert!(html.contains("Failed to read log t-14-01-01.log"));
+        assert!(!html.contains("src=\"http") && !html.contains("<link"));
+    }
+}
@@ -0,0 +1,351 @@
+use encoding_rs::{Encoding, WINDOWS_1252};
+use std::{
+    collections::HashSet,
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+const RTF_SIGNATURE: &[u8] = b"{\\rtf";
+/// destinations holding no visible text of output
+const HIDDEN_DESTINATIONS: [&str; 13] = [
+    "fonttbl",
+    "colortbl",
+    "stylesheet",
+    "info",
+    "pict",
+    "listtable",
+    "listoverridetable",
+    "rsidtbl",
+    "generator",
+    "xmlnstbl",
+    "themedata",
+    "datastore",
+    "fldinst",
+];
+
+/// visible text of rtf output, one entry per line
+#[derive(Debug, PartialEq)]
+pub struct RtfText {
+    /// lines in page header, where sas puts titles
+    pub titles: Vec<String>,
+    /// lines in page footer, where sas puts footnotes
+    pub footnotes: Vec<String>,
+    pub body: Vec<String>,
+    /// pages with text in body
+    pub pages: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Destination {
+    Body,
+    Header,
+    Footer,
+    Hidden,
+}
+
+/// read titles, footnotes and body text of rtf file, titles and footnotes written into
+/// body by `bodytitle` are left in body
+pub fn read_text<P: AsRef<Path>>(file: P) -> io::Result<RtfText> {
+    let content = fs::read(file)?;
+    if !content.starts_with(RTF_SIGNATURE) {
+        return Err(io::Error::new(ErrorKind::InvalidData, "not a rtf file"));
+    }
+    Ok(parse(&String::from_utf8_lossy(&content)))
+}
+
+/// title lines of rtf file in document order, repeated ones kept, body lines are given when
+/// there is no title in page header
+pub fn read_titles<P: AsRef<Path>>(file: P) -> io::Result<Vec<String>> {
+    let content = fs::read(file)?;
+    if !content.starts_with(RTF_SIGNATURE) {
+        return Err(io::Error::new(ErrorKind::InvalidData, "not a rtf file"));
+    }
+    Ok(titles(&String::from_utf8_lossy(&content)))
+}
+
+fn parse(content: &str) -> RtfText {
+    scan(content).finish()
+}
+
+fn titles(content: &str) -> Vec<String> {
+    let parser = scan(content);
+    let text = match parser.header.trim().is_empty() {
+        true => &parser.body,
+        false => &parser.header,
+    };
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+fn scan(content: &str) -> Parser {
+    let mut parser = Parser::new();
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                parser.flush();
+                parser.groups.push(parser.destination);
+            }
+            '}' => {
+                parser.flush();
+                parser.destination = parser.groups.pop().unwrap_or(Destination::Body);
+            }
+            '\\' => {
+                let Some(&next) = chars.peek() else {
+                    break;
+                };
+                if !next.is_ascii_alphabetic() {
+                    chars.next();
+                    match next {
+                        '*' => {
+                            parser.flush();
+                            parser.destination = Destination::Hidden;
+                        }
+                        '\'' => {
+                            let hex = chars.by_ref().take(2).collect::<String>();
+                            if let Ok(byte) = u8::from_str_radix(&hex, 16) {
+                                parser.byte(byte);
+                            }
+                        }
+                        '~' => parser.text(' '),
+                        '\\' | '{' | '}' => parser.text(next),
+                        _ => {}
+                    }
+                    continue;
+                }
+                let mut word = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphabetic()) {
+                    word.push(c);
+                    chars.next();
+                }
+                let mut param = String::new();
+                if chars.peek() == Some(&'-') {
+                    param.push('-');
+                    chars.next();
+                }
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                    param.push(c);
+                    chars.next();
+                }
+                // a space only delimits the control word
+                if chars.peek() == Some(&' ') {
+                    chars.next();
+                }
+                parser.control(&word, param.parse().ok());
+            }
+            '\r' | '\n' => {}
+            _ => parser.text(c),
+        }
+    }
+    parser.flush();
+    parser
+}
+
+struct Parser {
+    destination: Destination,
+    groups: Vec<Destination>,
+    body: String,
+    header: String,
+    footer: String,
+    /// characters a reader without unicode support uses after `\u`
+    unicode_fallback: usize,
+    /// code page of `\'xx` bytes, declared by `\ansicpg`
+    encoding: &'static Encoding,
+    /// `\'xx` bytes not decoded yet, a character of double byte code pages takes two of them
+    bytes: Vec<u8>,
+    skip: usize,
+    pages: usize,
+    /// body text is found since the last page break
+    page_started: bool,
+}
+
+impl Parser {
+    fn new() -> Parser {
+        Parser {
+            destination: Destination::Body,
+            groups: vec![],
+            body: String::new(),
+            header: String::new(),
+            footer: String::new(),
+            // one character unless `\uc` says otherwise
+            unicode_fallback: 1,
+            encoding: WINDOWS_1252,
+            bytes: vec![],
+            skip: 0,
+            pages: 0,
+            page_started: false,
+        }
+    }
+
+    fn control(&mut self, word: &str, param: Option<i32>) {
+        self.flush();
+        match word {
+            "par" | "line" | "cell" | "row" => self.push('\n'),
+            // sas starts every page with a new section
+            "sect" | "page" => {
+                self.push('\n');
+                if self.destination == Destination::Body {
+                    self.page_started = false;
+                }
+            }
+            "tab" => self.push(' '),
+            "emdash" | "endash" => self.push('-'),
+            "lquote" | "rquote" => self.push('\''),
+            "ldblquote" | "rdblquote" => self.push('"'),
+            "ansicpg" => self.encoding = code_page(param.unwrap_or_default()),
+            "uc" => self.unicode_fallback = param.unwrap_or(1).max(0) as usize,
+            "u" => {
+                let code = param.unwrap_or_default();
+                // code points above 32767 are written as negative numbers
+                let code = if code < 0 { code + 65536 } else { code };
+                if let Some(c) = char::from_u32(code as u32) {
+                    self.push(c);
+                }
+                self.skip = self.unicode_fallback;
+            }
+            "header" | "headerl" | "headerr" | "headerf" => self.destination = Destination::Header,
+            "footer" | "footerl" | "footerr" | "footerf" => self.destination = Destination::Footer,
+            _ if HIDDEN_DESTINATIONS.contains(&word) => self.destination = Destination::Hidden,
+            _ => {}
+        }
+    }
+
+    /// text character, skipped if it is the fallback of a unicode character
+    fn text(&mut self, c: char) {
+        self.flush();
+        if self.skip > 0 {
+            self.skip -= 1;
+            return;
+        }
+        self.push(c);
+    }
+
+    /// byte of `\'xx`, kept until the bytes around it are decoded together
+    fn byte(&mut self, byte: u8) {
+        if self.skip > 0 {
+            self.skip -= 1;
+            return;
+        }
+        self.bytes.push(byte);
+    }
+
+    fn flush(&mut self) {
+        if self.bytes.is_empty() {
+            return;
+        }
+        let bytes = std::mem::take(&mut self.bytes);
+        let (text, _) = self.encoding.decode_without_bom_handling(&bytes);
+        for c in text.chars() {
+            self.push(c);
+        }
+    }
+
+    fn push(&mut self, c: char) {
+        match self.destination {
+            Destination::Body => {
+                if !self.page_started && !c.is_whitespace() {
+                    self.pages += 1;
+                    self.page_started = true;
+                }
+                self.body.push(c)
+            }
+            Destination::Header => self.header.push(c),
+            Destination::Footer => self.footer.push(c),
+            Destination::Hidden => {}
+        }
+    }
+
+    fn finish(self) -> RtfText {
+        RtfText {
+            titles: unique(lines(&self.header)),
+            footnotes: unique(lines(&self.footer)),
+            body: lines(&self.body),
+            pages: self.pages,
+        }
+    }
+}
+
+/// encoding of windows code page, single byte western one if it is not known
+fn code_page(code_page: i32) -> &'static Encoding {
+    let label = match code_page {
+        936 | 20936 => "gbk".to_string(),
+        950 => "big5".into(),
+        932 => "shift_jis".into(),
+        949 => "euc-kr".into(),
+        65001 => "utf-8".into(),
+        874 | 1250..=1258 => format!("windows-{}", code_page),
+        _ => return WINDOWS_1252,
+    };
+    Encoding::for_label(label.as_bytes()).unwrap_or(WINDOWS_1252)
+}
+
+/// non-empty lines with whitespace collapsed
+fn lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// header and footer repeat on every page so only the first occurrence of a line is kept
+fn unique(lines: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    lines
+        .into_iter()
+        .filter(|line| seen.insert(line.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let content = r"{\rtf1\ansi\ansicpg1252\uc1{\fonttbl{\f1\fswiss Arial;}}{\colortbl;\red0\green0\blue0;}
+{\info{\title t-14-01-01}}
+\sectd{\header\pard\plain\ql{Protocol: AK112-303\cell}{Page {\field{\*\fldinst PAGE}{\fldrslt 1}}\cell}\row
+\pard\qc{Table 14.1.1  Summary of\~Demographics\par}{Safety Set\par}}
+{\footer\pard\ql{Note: Percentages are based on N.\par}{\u26032?\u25968?: Program t-14-01-01.sas\par}}
+{\pard\plain Age (years)\cell n\cell 120\cell\row}
+\sect\sectd{\header\pard{Table 14.1.1  Summary of Demographics\par}}
+{\pard\plain Sex\cell\row}
+{\pard\plain n\cell\row}
+}";
+        let text = parse(content);
+        assert_eq!(
+            text.titles,
+            vec![
+                "Protocol: AK112-303",
+                "Page 1",
+                "Table 14.1.1 Summary of Demographics",
+                "Safety Set"
+            ]
+        );
+        assert_eq!(
+            text.footnotes,
+            vec![
+                "Note: Percentages are based on N.",
+                "新数: Program t-14-01-01.sas"
+            ]
+        );
+        // body rows may repeat, eg. `n` of every parameter
+        assert_eq!(text.body, vec!["Age (years)", "n", "120", "Sex", "n"]);
+        assert_eq!(text.pages, 2);
+        let titles = titles(content);
+        assert_eq!(titles[2], "Table 14.1.1 Summary of Demographics");
+        assert_eq!(titles[4], "Table 14.1.1 Summary of Demographics");
+
+        let content = r"{\rtf1\ansi\ansicpg936\uc1{\header\pard{\'b1\'ed 14.1.1 \'b1\'ed\'b8\'f1\par}}
+{\footer\pard\uc2{\u34920\'b1\'ed\'b8\'f1 n\par}}}";
+        let text = parse(content);
+        assert_eq!(text.titles, vec!["表 14.1.1 表格"]);
+        // fallback of `\u` is counted in bytes, two for a character of double byte code pages
+        assert_eq!(text.footnotes, vec!["表格 n"]);
+        let content = r"{\rtf1\ansi\ansicpg1252{\header\pard{caf\'e9\par}}}";
+        assert_eq!(parse(content).titles, vec!["café"]);
+    }
+}
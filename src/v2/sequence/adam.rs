@@ -53,10 +53,12 @@ impl AdamSequenceAuditor {
         };
         let kind = FileType::Code;
         let name = filename(&self.item, &group, &kind);
+        let (status, evidence) = auditing(code, None, "");
         SequenceResult {
             name,
             kind,
-            status: auditing(code, None, ""),
+            status,
+            evidence,
             group,
             modified_at: code.map(|f| f.modified_at),
         }
@@ -73,10 +75,12 @@ impl AdamSequenceAuditor {
         };
         let kind = FileType::Data;
         let name = filename(&self.item, &group, &kind);
+        let (status, evidence) = auditing(dataset, code, "Code later than data");
         SequenceResult {
             name,
             kind,
-            status: auditing(dataset, code, "Code later than data"),
+            status,
+            evidence,
             group,
             modified_at: dataset.map(|f| f.modified_at),
         }
@@ -87,10 +91,12 @@ impl AdamSequenceAuditor {
         let dataset = self.production.dataset.as_ref();
         let kind = FileType::Xpt;
         let name = filename(&self.item, &Group::Production, &kind);
+        let (status, evidence) = auditing(xpt, dataset, "Xpt later than data");
         SequenceResult {
             name,
             kind,
-            status: auditing(xpt, dataset, "Xpt later than data"),
+            status,
+            evidence,
             // Here we assume the main output always belongs to Production;
             // adjust the group if needed.
             group: Group::Production,
@@ -103,17 +109,23 @@ impl AdamSequenceAuditor {
         let prod_dataset = self.production.dataset.as_ref();
         let val_dataset = self.validation.dataset.as_ref();
 
-        let mut status = auditing(base, val_dataset, "Qc later than val dataset");
+        let mut verdict = auditing(base, val_dataset, "Qc later than val dataset");
 
-        if !status.is_pass() {
-            status = auditing(base, prod_dataset, "Qc later than prod dataset");
+        if !verdict.0.is_pass() {
+            verdict = auditing(base, prod_dataset, "Qc later than prod dataset");
         }
+        let (status, evidence) = verdict;
         let kind = FileType::Qc;
-        let name = filename(&self.item, &Group::Validation, &kind);
+        // qc result may be saved in any of the formats of study
+        let name = base.map_or_else(
+            || filename(&self.item, &Group::Validation, &kind),
+            |f| f.name.clone(),
+        );
         SequenceResult {
             name,
             kind,
             status,
+            evidence,
             group: Group::Validation,
             modified_at: base.as_ref().map(|f| f.modified_at),
         }
@@ -134,10 +146,12 @@ impl AdamSequenceAuditor {
         };
         let kind = FileType::Log;
         let name = filename(&self.item, &group, &kind);
+        let (status, evidence) = auditing(log, product, message);
         SequenceResult {
             name,
             kind,
-            status: auditing(log, product, message),
+            status,
+            evidence,
             group,
             modified_at: log.as_ref().map(|f| f.modified_at),
         }
@@ -1,7 +1,7 @@
 use super::{adam::AdamSequenceAuditor, sdtm::SdtmSequenceAuditor, tfl::TflSequenceAuditor};
 use crate::v2::{
     category::FileType,
-    inspect::result::Status,
+    inspect::{evidence::Evidence, result::Status},
     investigator::{investigator::File, investigator::Investigator},
     Group, Kind,
 };
@@ -21,6 +21,7 @@ pub struct SequenceResult {
     pub group: Group,
     #[serde(rename = "modifiedAt")]
     pub modified_at: Option<DateTime<Local>>,
+    pub evidence: Option<Evidence>,
 }
 
 #[derive(Debug, Serialize)]
@@ -56,36 +57,43 @@ pub fn total_status(results: &[SequenceResult]) -> Status {
     }
 }
 
+/// status of base file, with evidence if it failed
 pub fn auditing<P: AsRef<Path>>(
     base: Option<&File<P>>,
     compare: Option<&File<P>>,
     failed_message: &str,
-) -> Status {
+) -> (Status, Option<Evidence>) {
     match base {
         Some(base) => {
             if let Some(compare) = compare {
                 if compare.modified_at.ge(&base.modified_at) {
-                    Status::Failed(failed_message.into())
+                    (
+                        Status::Failed(failed_message.into()),
+                        Some(Evidence::sequence(base, compare, failed_message)),
+                    )
                 } else {
-                    Status::Pass
+                    (Status::Pass, None)
                 }
             } else {
-                Status::Pass
+                (Status::Pass, None)
             }
         }
-        None => Status::Missing,
+        None => (Status::Missing, None),
     }
 }
 
-pub fn code_auditing<P: AsRef<Path>>(code: Option<&File<P>>) -> Status {
+pub fn code_auditing<P: AsRef<Path>>(code: Option<&File<P>>) -> (Status, Option<Evidence>) {
     match code {
         Some(code) => {
             if code.modified_at.eq(&code.created_at) {
-                Status::Failed("File not start editing".into())
+                (
+                    Status::Failed("File not start editing".into()),
+                    Some(Evidence::code(code)),
+                )
             } else {
-                Status::Pass
+                (Status::Pass, None)
             }
         }
-        None => Status::Missing,
+        None => (Status::Missing, None),
     }
 }
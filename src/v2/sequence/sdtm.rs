@@ -66,10 +66,12 @@ impl SdtmSequenceAuditor {
         };
         let kind = FileType::Code;
         let name = filename(&self.item, &group, &kind);
+        let (status, evidence) = code_auditing(code);
         SequenceResult {
             name,
             kind,
-            status: code_auditing(code),
+            status,
+            evidence,
             group,
             modified_at: code.map(|f| f.modified_at),
         }
@@ -86,10 +88,12 @@ impl SdtmSequenceAuditor {
             Group::Production => self.production.code.as_ref(),
             Group::Validation => self.validation.code.as_ref(),
         };
+        let (status, evidence) = auditing(base, compare, "Code later than data");
         SequenceResult {
             name,
             kind,
-            status: auditing(base, compare, "Code later than data"),
+            status,
+            evidence,
             group,
             modified_at: base.as_ref().map(|f| f.modified_at),
         }
@@ -106,26 +110,30 @@ impl SdtmSequenceAuditor {
             Group::Production => self.production.main_data.as_ref(),
             Group::Validation => self.validation.main_data.as_ref(),
         };
+        let (status, evidence) = auditing(base, compare, "Main later than supp");
         SequenceResult {
             name,
             kind,
-            status: auditing(base, compare, "Main later than supp"),
+            status,
+            evidence,
             group,
-            modified_at: self.production.supp_data.as_ref().map(|f| f.modified_at),
+            modified_at: base.map(|f| f.modified_at),
         }
     }
 
     fn audit_main_xpt(&self) -> SequenceResult {
         let kind = FileType::Xpt;
         let name = filename(&self.item, &Group::Production, &kind);
+        let (status, evidence) = auditing(
+            self.production.main_xpt.as_ref(),
+            self.production.main_data.as_ref(),
+            "Data later than xpt",
+        );
         SequenceResult {
             name,
             kind,
-            status: auditing(
-                self.production.main_xpt.as_ref(),
-                self.production.main_data.as_ref(),
-                "Data later than xpt",
-            ),
+            status,
+            evidence,
             group: Group::Production,
             modified_at: self.production.main_xpt.as_ref().map(|f| f.modified_at),
         }
@@ -134,14 +142,16 @@ impl SdtmSequenceAuditor {
     fn audit_supp_xpt(&self) -> SequenceResult {
         let kind = FileType::Xpt;
         let name = filename(&format!("supp{}", &self.item), &Group::Production, &kind);
+        let (status, evidence) = auditing(
+            self.production.supp_xpt.as_ref(),
+            self.production.supp_data.as_ref(),
+            "Data later than xpt",
+        );
         SequenceResult {
             name,
             kind,
-            status: auditing(
-                self.production.supp_xpt.as_ref(),
-                self.production.supp_data.as_ref(),
-                "Data later than xpt",
-            ),
+            status,
+            evidence,
             group: Group::Production,
             modified_at: self.production.supp_xpt.as_ref().map(|f| f.modified_at),
         }
@@ -149,15 +159,21 @@ impl SdtmSequenceAuditor {
 
     fn audit_main_qc(&self) -> SequenceResult {
         let kind = FileType::Qc;
-        let name = filename(&self.item, &Group::Validation, &kind);
+        // qc result may be saved in any of the formats of study
+        let name = self.validation.main_qc.as_ref().map_or_else(
+            || filename(&self.item, &Group::Validation, &kind),
+            |f| f.name.clone(),
+        );
+        let (status, evidence) = auditing(
+            self.validation.main_qc.as_ref(),
+            self.validation.main_data.as_ref(),
+            "Data later than QcResult",
+        );
         SequenceResult {
             name,
             kind,
-            status: auditing(
-                self.validation.main_qc.as_ref(),
-                self.validation.main_data.as_ref(),
-                "Data later than QcResult",
-            ),
+            status,
+            evidence,
             group: Group::Validation,
             modified_at: self.validation.main_qc.as_ref().map(|f| f.modified_at),
         }
@@ -165,15 +181,20 @@ impl SdtmSequenceAuditor {
 
     fn audit_supp_qc(&self) -> SequenceResult {
         let kind = FileType::Qc;
-        let name = filename(&format!("supp{}", &self.item), &Group::Validation, &kind);
+        let name = self.validation.supp_qc.as_ref().map_or_else(
+            || filename(&format!("supp{}", &self.item), &Group::Validation, &kind),
+            |f| f.name.clone(),
+        );
+        let (status, evidence) = auditing(
+            self.validation.supp_qc.as_ref(),
+            self.validation.main_data.as_ref(),
+            "Data later than QcResult",
+        );
         SequenceResult {
             name,
             kind,
-            status: auditing(
-                self.validation.supp_qc.as_ref(),
-                self.validation.main_data.as_ref(),
-                "Data later than QcResult",
-            ),
+            status,
+            evidence,
             group: Group::Validation,
             modified_at: self.validation.supp_qc.as_ref().map(|f| f.modified_at),
         }
@@ -200,10 +221,12 @@ impl SdtmSequenceAuditor {
             Group::Production => "Data later than log",
             Group::Validation => "QcResult later than log",
         };
+        let (status, evidence) = auditing(base, compare, message);
         SequenceResult {
             name,
             kind,
-            status: auditing(base, compare, message),
+            status,
+            evidence,
             group,
             modified_at: base.map(|f| f.modified_at),
         }
@@ -53,10 +53,12 @@ impl TflSequenceAuditor {
             Group::Production => self.production.code.as_ref(),
             Group::Validation => self.validation.code.as_ref(),
         };
+        let (status, evidence) = auditing(code, None, "");
         SequenceResult {
             name: filename(&self.item, &group, &kind),
             kind,
-            status: auditing(code, None, ""),
+            status,
+            evidence,
             group,
             modified_at: code.map(|f| f.modified_at),
         }
@@ -73,10 +75,12 @@ impl TflSequenceAuditor {
             Group::Validation => self.validation.code.as_ref(),
         };
 
+        let (status, evidence) = auditing(base, compare, "Code later than data");
         SequenceResult {
             name: filename(&self.item, &group, &kind),
             kind,
-            status: auditing(base, compare, "Code later than data"),
+            status,
+            evidence,
             group,
             modified_at: base.as_ref().map(|f| f.modified_at),
         }
@@ -87,10 +91,16 @@ impl TflSequenceAuditor {
         let base = self.production.output.as_ref();
         let compare = self.production.code.as_ref();
 
+        let (status, evidence) = auditing(base, compare, "Code later than output");
         SequenceResult {
-            name: filename(&self.item, &Group::Production, &kind),
+            // output may be saved in any of the formats of study
+            name: base.map_or_else(
+                || filename(&self.item, &Group::Production, &kind),
+                |f| f.name.clone(),
+            ),
             kind,
-            status: auditing(base, compare, "Code later than output"),
+            status,
+            evidence,
             group: Group::Production,
             modified_at: base.as_ref().map(|f| f.modified_at),
         }
@@ -102,16 +112,21 @@ impl TflSequenceAuditor {
         let prod_dataset = self.production.dataset.as_ref();
         let val_dataset = self.validation.dataset.as_ref();
 
-        let mut status = auditing(base, val_dataset, "Qc later than val dataset");
+        let mut verdict = auditing(base, val_dataset, "Qc later than val dataset");
 
-        if !status.is_pass() {
-            status = auditing(base, prod_dataset, "Qc later than prod dataset");
+        if !verdict.0.is_pass() {
+            verdict = auditing(base, prod_dataset, "Qc later than prod dataset");
         }
+        let (status, evidence) = verdict;
 
         SequenceResult {
-            name: filename(&self.item, &Group::Validation, &kind),
+            name: base.map_or_else(
+                || filename(&self.item, &Group::Validation, &kind),
+                |f| f.name.clone(),
+            ),
             kind,
             status,
+            evidence,
             group: Group::Validation,
             modified_at: base.as_ref().map(|f| f.modified_at),
         }
@@ -131,10 +146,12 @@ impl TflSequenceAuditor {
             Group::Production => "Output later than log",
             Group::Validation => "Qc later than log",
         };
+        let (status, evidence) = auditing(base, compare, message);
         SequenceResult {
             name: filename(&self.item, &group, &kind),
             kind,
-            status: auditing(base, compare, message),
+            status,
+            evidence,
             group,
             modified_at: base.as_ref().map(|f| f.modified_at),
         }
@@ -0,0 +1,296 @@
+use super::{
+    bundle::verify_bundle,
+    category::{Group, Kind},
+    error::Error,
+    inspect::inspector::{inspect_with, log_detail, qc_detail, sequence_detail, InspectOption},
+    investigator::investigator::InvestigatorParam,
+    product::list_products,
+    read_config,
+};
+use serde::Serialize;
+use serde_json::json;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use tiny_http::{Header, Method, Response, Server};
+
+/// serve v2 functions as json over http, studies are looked up under root
+pub fn serve<P: AsRef<Path>>(addr: &str, root: P) -> std::io::Result<()> {
+    let server = Server::http(addr).map_err(std::io::Error::other)?;
+    let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+        .expect("valid content type header");
+    for request in server.incoming_requests() {
+        let (code, body) = match request.method() {
+            Method::Get => handle(root.as_ref(), request.url()),
+            _ => (405, error("only GET is supported")),
+        };
+        let response = Response::from_string(body)
+            .with_status_code(code)
+            .with_header(content_type.clone());
+        // client may have gone away, keep serving others
+        request.respond(response).ok();
+    }
+    Ok(())
+}
+
+/// route request url to v2 function, returns status code and json body
+///
+/// | path        | parameters                                         |
+/// | ----------- | -------------------------------------------------- |
+/// | `/products` |                                                    |
+/// | `/config`   | `file`, `kind`                                     |
+/// | `/inspect`  | study, `config`, `kind`, `ignore`                  |
+/// | `/log`      | study, `item`, `kind`, `group`                     |
+/// | `/qc`       | study, `item`, `kind`, `ignore`                    |
+/// | `/sequence` | study, `item`, `kind`, `supp`                      |
+/// | `/bundle`   | study, `config`                                    |
+///
+/// study parameters are `product`, `trial` and `purpose`, `ignore` is comma separated,
+/// files (`file`, `config`, `previous`) are resolved against root and must stay under it
+pub fn handle(root: &Path, url: &str) -> (u16, String) {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let query = parse_query(query);
+    match route(root, path, &query) {
+        Ok(body) => (200, body),
+        Err(RouteError::NotFound) => (404, error(&format!("{} not found", path))),
+        Err(RouteError::BadRequest(message)) => (400, error(&message)),
+        Err(RouteError::Failed(message)) => (500, error(&message)),
+    }
+}
+
+enum RouteError {
+    NotFound,
+    BadRequest(String),
+    Failed(String),
+}
+
+impl From<Error> for RouteError {
+    fn from(value: Error) -> Self {
+        RouteError::Failed(value.to_string())
+    }
+}
+
+type Query = HashMap<String, String>;
+
+fn route(root: &Path, path: &str, query: &Query) -> Result<String, RouteError> {
+    match path.trim_end_matches('/') {
+        "/products" => body(&list_products(root)?),
+        "/config" => body(&read_config(file(root, query, "file")?, &kind(query)?)?),
+        "/inspect" => {
+            let option = InspectOption {
+                qc_ignore: ignore(query),
+                cache: flag(query, "cache"),
+                timeline: flag(query, "timeline"),
+                p21: flag(query, "p21"),
+                previous_spec: query
+                    .contains_key("previous")
+                    .then(|| file(root, query, "previous"))
+                    .transpose()?,
+                ..Default::default()
+            };
+            let config = file(root, query, "config")?;
+            let inspection = inspect_with(
+                &study(root, query)?,
+                config.as_path(),
+                &kind(query)?,
+                &option,
+            )?;
+            body(&inspection.results)
+        }
+        "/log" => body(&log_detail(
+            &study(root, query)?,
+            param(query, "item")?,
+            &kind(query)?,
+            &group(query)?,
+        )?),
+        "/qc" => body(&qc_detail(
+            &study(root, query)?,
+            param(query, "item")?,
+            &kind(query)?,
+            &ignore(query),
+        )?),
+        "/sequence" => body(&sequence_detail(
+            &study(root, query)?,
+            param(query, "item")?,
+            flag(query, "supp"),
+            &kind(query)?,
+        )?),
+        "/bundle" => body(&verify_bundle(
+            &study(root, query)?,
+            file(root, query, "config")?.as_path(),
+        )?),
+        _ => Err(RouteError::NotFound),
+    }
+}
+
+fn body<T: Serialize>(value: &T) -> Result<String, RouteError> {
+    serde_json::to_string(value).map_err(|e| RouteError::Failed(e.to_string()))
+}
+
+fn error(message: &str) -> String {
+    json!({ "error": message }).to_string()
+}
+
+fn param<'a>(query: &'a Query, name: &str) -> Result<&'a str, RouteError> {
+    query
+        .get(name)
+        .map(|value| value.as_str())
+        .ok_or_else(|| RouteError::BadRequest(format!("missing parameter {}", name)))
+}
+
+/// study directories are joined to root, so parameters must not leave it
+fn study<'a>(root: &'a Path, query: &Query) -> Result<InvestigatorParam<&'a Path>, RouteError> {
+    Ok(InvestigatorParam {
+        product: segment(query, "product")?.into(),
+        trial: segment(query, "trial")?.into(),
+        purpose: segment(query, "purpose")?.into(),
+        root,
+    })
+}
+
+/// parameter used as a single directory name, without separators or parent references
+fn segment<'a>(query: &'a Query, name: &str) -> Result<&'a str, RouteError> {
+    let value = param(query, name)?;
+    match value.is_empty() || value.contains(['/', '\\', ':']) || value.contains("..") {
+        true => Err(RouteError::BadRequest(format!(
+            "invalid {} {}",
+            name, value
+        ))),
+        false => Ok(value),
+    }
+}
+
+/// file parameter resolved against root, files outside root are not served
+fn file(root: &Path, query: &Query, name: &str) -> Result<PathBuf, RouteError> {
+    let value = param(query, name)?;
+    let invalid = || RouteError::BadRequest(format!("invalid {} {}", name, value));
+    let root = root.canonicalize().map_err(|_| invalid())?;
+    let path = root.join(value).canonicalize().map_err(|_| invalid())?;
+    match path.starts_with(&root) {
+        true => Ok(path),
+        false => Err(invalid()),
+    }
+}
+
+fn kind(query: &Query) -> Result<Kind, RouteError> {
+    match param(query, "kind")?.to_lowercase().as_str() {
+        "sdtm" => Ok(Kind::SDTM),
+        "adam" => Ok(Kind::ADaM),
+        "tfls" | "tfl" => Ok(Kind::TFLs),
+        other => Err(RouteError::BadRequest(format!("invalid kind {}", other))),
+    }
+}
+
+fn group(query: &Query) -> Result<Group, RouteError> {
+    match param(query, "group")?.to_lowercase().as_str() {
+        "production" => Ok(Group::Production),
+        "validation" => Ok(Group::Validation),
+        other => Err(RouteError::BadRequest(format!("invalid group {}", other))),
+    }
+}
+
+fn flag(query: &Query, name: &str) -> bool {
+    query.get(name).is_some_and(|value| value.eq("true"))
+}
+
+fn ignore(query: &Query) -> Vec<String> {
+    query
+        .get("ignore")
+        .map(|ignore| {
+            ignore
+                .split(',')
+                .filter(|pattern| !pattern.is_empty())
+                .map(|pattern| pattern.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_query(query: &str) -> Query {
+    query
+        .split('&')
+        .filter_map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (!key.is_empty()).then(|| (decode(key), decode(value)))
+        })
+        .collect()
+}
+
+/// percent decoding of query component, `+` stands for space
+fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'+' => decoded.push(b' '),
+            b'%' if index + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[index + 1..index + 3]).unwrap_or_default();
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        decoded.push(byte);
+                        index += 2;
+                    }
+                    Err(_) => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("D%3A%5CStudies+ak112"), r"D:\Studies ak112");
+        assert_eq!(decode("100%"), "100%");
+        assert_eq!(decode("%E6%B5%8B"), "测");
+    }
+
+    #[test]
+    fn test_handle() -> anyhow::Result<()> {
+        let root = std::env::temp_dir().join("inspector_server_test");
+        fs::create_dir_all(root.join("ak112").join("303").join("stats").join("CSR"))?;
+        let (code, body) = handle(&root, "/products");
+        assert_eq!(code, 200);
+        assert!(body.contains("ak112"));
+        let (code, body) = handle(
+            &root,
+            "/sequence?product=ak112&trial=303&purpose=CSR&item=t-14-01-01&kind=TFLs",
+        );
+        assert_eq!(code, 200);
+        assert!(body.contains("Missing"));
+        let (code, _) = handle(&root, "/log?product=ak112&trial=303&purpose=CSR&item=ae");
+        assert_eq!(code, 400);
+        let (code, _) = handle(
+            &root,
+            "/log?product=..&trial=303&purpose=CSR&item=ae&kind=sdtm",
+        );
+        assert_eq!(code, 400);
+        let (code, _) = handle(
+            &root,
+            "/bundle?product=ak112&trial=303%2F..%2F..&purpose=CSR&config=top.xlsx",
+        );
+        assert_eq!(code, 400);
+        fs::write(root.join("top.xlsx"), "")?;
+        let (code, body) = handle(&root, "/config?file=..%2Ftop.xlsx&kind=tfls");
+        assert_eq!(code, 400);
+        assert!(body.contains("invalid file"));
+        let (code, _) = handle(&root, "/config?file=ak112%2F..%2F..%2Ftop.xlsx&kind=tfls");
+        assert_eq!(code, 400);
+        let (code, body) = handle(&root, "/config?file=top.xlsx&kind=tfls");
+        assert_eq!(code, 500);
+        assert!(!body.contains("invalid file"));
+        let (code, _) = handle(&root, "/unknown");
+        assert_eq!(code, 404);
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+}
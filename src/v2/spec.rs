@@ -0,0 +1,335 @@
+use super::{
+    category::{Group, Kind},
+    config::{
+        reader::{read_config, Config},
+        variable::Variable,
+    },
+    error::Result,
+    inspect::{evidence::Evidence, result::InspectionResult, result::Status},
+    investigator::investigator::{File, Investigator},
+    stage::file_version,
+};
+use serde::Serialize;
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub enum VariableChangeKind {
+    Added,
+    Removed,
+    Label,
+    Type,
+    Length,
+    /// variable moved between main domain and supp
+    Allocation,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct VariableChange {
+    pub variable: String,
+    pub change: VariableChangeKind,
+    pub previous: Option<String>,
+    pub current: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DomainChange {
+    pub domain: String,
+    /// whether domain has supp in current spec, none if it is unchanged
+    pub supp: Option<bool>,
+    pub variables: Vec<VariableChange>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SpecDiff {
+    #[serde(rename = "previousVersion")]
+    pub previous_version: Option<String>,
+    #[serde(rename = "currentVersion")]
+    pub current_version: Option<String>,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<DomainChange>,
+}
+
+impl SpecDiff {
+    /// domains whose datasets have to be produced again, added ones included
+    pub fn changed_domains(&self) -> HashSet<String> {
+        self.added
+            .iter()
+            .chain(self.changed.iter().map(|change| &change.domain))
+            .map(|domain| domain.to_uppercase())
+            .collect()
+    }
+}
+
+/// order spec files by version in their names, latest first, files without version go last
+pub fn sort_specs<P: AsRef<Path>>(files: &mut [P]) {
+    files.sort_by_key(|file| std::cmp::Reverse(version_key(file.as_ref())));
+}
+
+/// spec file with the highest version in its name
+pub fn latest_spec<P: AsRef<Path>>(files: &[P]) -> Option<&P> {
+    files.iter().max_by_key(|file| version_key(file.as_ref()))
+}
+
+/// numbers of version in file name, `v0.10` is later than `v0.9`
+fn version_key(file: &Path) -> Vec<u32> {
+    file_version(file)
+        .map(|version| {
+            version[1..]
+                .split('.')
+                .filter_map(|number| number.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// compare domains and their variables defined in two versions of spec
+pub fn diff_spec<P: AsRef<Path>>(previous: P, current: P, kind: &Kind) -> Result<SpecDiff> {
+    let before = read_config(previous.as_ref(), kind)?;
+    let after = read_config(current.as_ref(), kind)?;
+    let before_by_name = by_name(&before);
+    let after_by_name = by_name(&after);
+    let added = after
+        .iter()
+        .filter(|config| !before_by_name.contains_key(&config.name.to_uppercase()))
+        .map(|config| config.name.clone())
+        .collect();
+    let removed = before
+        .iter()
+        .filter(|config| !after_by_name.contains_key(&config.name.to_uppercase()))
+        .map(|config| config.name.clone())
+        .collect();
+    let changed = after
+        .iter()
+        .filter_map(|config| {
+            let previous = before_by_name.get(&config.name.to_uppercase())?;
+            let change = domain_change(previous, config);
+            match change.supp.is_none() && change.variables.is_empty() {
+                true => None,
+                false => Some(change),
+            }
+        })
+        .collect();
+    Ok(SpecDiff {
+        previous_version: file_version(previous.as_ref()),
+        current_version: file_version(current.as_ref()),
+        added,
+        removed,
+        changed,
+    })
+}
+
+/// flag results of changed domains whose production dataset is older than the current spec
+pub fn attach_spec_changes(
+    investigator: &Investigator,
+    kind: &Kind,
+    spec: &Path,
+    diff: &SpecDiff,
+    results: &mut [InspectionResult],
+) {
+    let spec = spec
+        .parent()
+        .zip(spec.file_name())
+        .and_then(|(dir, name)| investigator.snapshot(dir).file(&name.to_string_lossy()));
+    let Some(spec) = spec else {
+        return;
+    };
+    let domains = diff.changed_domains();
+    for result in results.iter_mut() {
+        if !domains.contains(&result.item.to_uppercase()) {
+            continue;
+        }
+        let dataset = match kind {
+            Kind::SDTM => investigator.sdtm_data_main_production(&result.item),
+            Kind::ADaM => investigator.adam_data(&result.item, &Group::Production),
+            Kind::TFLs => investigator.tfl_output(&result.item, &Group::Production),
+        };
+        result.spec_change = Some(spec_change(dataset.as_ref(), &spec, &mut result.evidence));
+    }
+}
+
+fn spec_change(
+    dataset: Option<&File<PathBuf>>,
+    spec: &File<PathBuf>,
+    evidence: &mut Vec<Evidence>,
+) -> Status {
+    match dataset {
+        Some(dataset) if dataset.modified_at.lt(&spec.modified_at) => {
+            evidence.push(Evidence::spec(dataset, spec));
+            Status::Failed("Spec changed after dataset produced".into())
+        }
+        Some(_) => Status::Pass,
+        None => Status::Missing,
+    }
+}
+
+fn by_name(configs: &[Config]) -> HashMap<String, &Config> {
+    configs
+        .iter()
+        .map(|config| (config.name.to_uppercase(), config))
+        .collect()
+}
+
+fn domain_change(previous: &Config, current: &Config) -> DomainChange {
+    let before = variables_by_name(&previous.variables);
+    let after = variables_by_name(&current.variables);
+    let mut variables = vec![];
+    for variable in current.variables.iter() {
+        let Some(old) = before.get(variable.name.as_str()) else {
+            variables.push(VariableChange {
+                variable: variable.name.clone(),
+                change: VariableChangeKind::Added,
+                previous: None,
+                current: None,
+            });
+            continue;
+        };
+        let attributes = [
+            (
+                VariableChangeKind::Label,
+                Some(old.label.clone()),
+                Some(variable.label.clone()),
+            ),
+            (
+                VariableChangeKind::Type,
+                old.data_type.map(|t| t.as_str().to_string()),
+                variable.data_type.map(|t| t.as_str().to_string()),
+            ),
+            (
+                VariableChangeKind::Length,
+                old.length.map(|length| length.to_string()),
+                variable.length.map(|length| length.to_string()),
+            ),
+            (
+                VariableChangeKind::Allocation,
+                Some(old.allocation.clone()),
+                Some(variable.allocation.clone()),
+            ),
+        ];
+        for (change, previous, current) in attributes {
+            if previous.ne(&current) {
+                variables.push(VariableChange {
+                    variable: variable.name.clone(),
+                    change,
+                    previous,
+                    current,
+                });
+            }
+        }
+    }
+    for variable in previous.variables.iter() {
+        if !after.contains_key(variable.name.as_str()) {
+            variables.push(VariableChange {
+                variable: variable.name.clone(),
+                change: VariableChangeKind::Removed,
+                previous: None,
+                current: None,
+            });
+        }
+    }
+    DomainChange {
+        domain: current.name.clone(),
+        supp: match previous.supp.eq(&current.supp) {
+            true => None,
+            false => Some(current.supp),
+        },
+        variables,
+    }
+}
+
+fn variables_by_name(variables: &[Variable]) -> HashMap<&str, &Variable> {
+    variables
+        .iter()
+        .map(|variable| (variable.name.as_str(), variable))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::{config::variable::VariableType, inspect::evidence::Rule};
+    use chrono::{Local, TimeZone};
+
+    fn variable(name: &str, length: usize, allocation: &str) -> Variable {
+        Variable {
+            name: name.into(),
+            label: String::new(),
+            data_type: Some(VariableType::Char),
+            length: Some(length),
+            allocation: allocation.into(),
+        }
+    }
+
+    #[test]
+    fn test_diff_spec() {
+        let mut specs = vec![
+            "AK112-303 SDTM Specification v0.9.xlsx",
+            "AK112-303 SDTM Specification.xlsx",
+            "AK112-303 SDTM Specification v0.10.xlsx",
+        ];
+        assert_eq!(latest_spec(&specs), Some(&specs[2]));
+        sort_specs(&mut specs);
+        assert_eq!(specs[0], "AK112-303 SDTM Specification v0.10.xlsx");
+        assert_eq!(specs[2], "AK112-303 SDTM Specification.xlsx");
+
+        let previous = Config {
+            name: "AE".into(),
+            variables: vec![
+                variable("AETERM", 200, "AE"),
+                variable("AESPID", 20, "AE"),
+                variable("AETRTEM", 1, "AE"),
+            ],
+            ..Default::default()
+        };
+        let current = Config {
+            name: "AE".into(),
+            supp: true,
+            variables: vec![
+                variable("AETERM", 100, "AE"),
+                variable("AETRTEM", 1, "SUPP"),
+                variable("AEDECOD", 200, "AE"),
+            ],
+            ..Default::default()
+        };
+        let change = domain_change(&previous, &current);
+        assert_eq!(change.supp, Some(true));
+        let changes = change
+            .variables
+            .iter()
+            .map(|change| (change.variable.as_str(), change.change))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                ("AETERM", VariableChangeKind::Length),
+                ("AETRTEM", VariableChangeKind::Allocation),
+                ("AEDECOD", VariableChangeKind::Added),
+                ("AESPID", VariableChangeKind::Removed),
+            ]
+        );
+        assert_eq!(change.variables[1].current, Some("SUPP".into()));
+        assert!(domain_change(&previous, &previous).variables.is_empty());
+
+        let file = |name: &str, hour: u32| File {
+            name: name.into(),
+            filepath: PathBuf::from(name),
+            created_at: Local.with_ymd_and_hms(2024, 3, 1, hour, 0, 0).unwrap(),
+            modified_at: Local.with_ymd_and_hms(2024, 3, 1, hour, 0, 0).unwrap(),
+        };
+        let spec = file("AK112-303 SDTM Specification v0.10.xlsx", 10);
+        let mut evidence = vec![];
+        assert_eq!(
+            spec_change(Some(&file("ae.sas7bdat", 11)), &spec, &mut evidence),
+            Status::Pass
+        );
+        assert_eq!(
+            spec_change(Some(&file("ae.sas7bdat", 9)), &spec, &mut evidence),
+            Status::Failed("Spec changed after dataset produced".into())
+        );
+        assert_eq!(evidence[0].rule, Rule::SpecChanged);
+        assert_eq!(spec_change(None, &spec, &mut evidence), Status::Missing);
+    }
+}
@@ -0,0 +1,457 @@
+use super::{
+    category::Kind,
+    config::reader::{read_config, Config},
+    error::Result,
+    inspect::result::{InspectionResult, Status},
+    investigator::investigator::{Investigator, InvestigatorParam},
+    study::sdtm_study,
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::Path};
+
+lazy_static! {
+    static ref FILE_VERSION: Regex = Regex::new(r"(?i)(?:\b|_)v(\d+(?:\.\d+)*)(?:\b|_)").unwrap();
+}
+
+/// stage of the process described in readme, in the order items go through
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    AcrfReview,
+    SpecReview,
+    Coding,
+    P21,
+    OutputReview,
+    Complete,
+}
+
+const SDTM_STAGES: [Stage; 6] = [
+    Stage::AcrfReview,
+    Stage::SpecReview,
+    Stage::Coding,
+    Stage::P21,
+    Stage::OutputReview,
+    Stage::Complete,
+];
+
+const ADAM_STAGES: [Stage; 5] = [
+    Stage::SpecReview,
+    Stage::Coding,
+    Stage::P21,
+    Stage::OutputReview,
+    Stage::Complete,
+];
+
+const TFL_STAGES: [Stage; 4] = [
+    Stage::SpecReview,
+    Stage::Coding,
+    Stage::OutputReview,
+    Stage::Complete,
+];
+
+impl Stage {
+    /// stages items of kind go through
+    pub fn stages(kind: &Kind) -> &'static [Stage] {
+        match kind {
+            Kind::SDTM => &SDTM_STAGES,
+            Kind::ADaM => &ADAM_STAGES,
+            Kind::TFLs => &TFL_STAGES,
+        }
+    }
+
+    pub fn label(&self, kind: &Kind) -> &'static str {
+        match self {
+            Stage::AcrfReview => "aCRF Review",
+            Stage::SpecReview => match kind {
+                Kind::TFLs => "TOP Review",
+                _ => "Spec Review",
+            },
+            Stage::Coding => "Coding",
+            Stage::P21 => "P21",
+            Stage::OutputReview => "Output Review",
+            Stage::Complete => "Complete",
+        }
+    }
+
+    /// stage following this one for kind, none once complete
+    pub fn next(&self, kind: &Kind) -> Option<Stage> {
+        let stages = Stage::stages(kind);
+        let index = stages.iter().position(|stage| stage.eq(self))?;
+        stages.get(index + 1).copied()
+    }
+
+    /// items move forward one stage at a time, and back to any earlier stage once artifacts change
+    pub fn can_transit(&self, to: &Stage, kind: &Kind) -> bool {
+        let stages = Stage::stages(kind);
+        let from = stages.iter().position(|stage| stage.eq(self));
+        let to = stages.iter().position(|stage| stage.eq(to));
+        match (from, to) {
+            (Some(from), Some(to)) => to == from + 1 || to < from,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub struct Transition {
+    pub from: Stage,
+    pub to: Stage,
+}
+
+/// every transition allowed for kind
+pub fn transitions(kind: &Kind) -> Vec<Transition> {
+    let stages = Stage::stages(kind);
+    stages
+        .iter()
+        .flat_map(|from| {
+            stages
+                .iter()
+                .filter(|to| from.can_transit(to, kind))
+                .map(|to| Transition {
+                    from: *from,
+                    to: *to,
+                })
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+pub struct ItemStage {
+    pub item: String,
+    pub stage: Stage,
+    pub next: Option<Stage>,
+    /// what keeps item from moving to the next stage
+    pub blockers: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StageReport {
+    /// earliest stage of items
+    pub stage: Stage,
+    /// version in name of spec file, eg. `v0.4`
+    #[serde(rename = "specVersion")]
+    pub spec_version: Option<String>,
+    pub items: Vec<ItemStage>,
+    pub transitions: Vec<Transition>,
+}
+
+/// blockers shared by every item of kind
+struct KindGate {
+    acrf: Vec<String>,
+    spec: Vec<String>,
+    p21: Vec<String>,
+}
+
+/// derive the stage of each config item from artifacts in study and results of inspection
+pub fn stage<P: AsRef<Path>>(
+    param: &InvestigatorParam<P>,
+    config_file: P,
+    kind: &Kind,
+    results: &[InspectionResult],
+) -> Result<StageReport> {
+    let config = read_config(config_file.as_ref(), kind)?;
+    let spec_version = file_version(config_file.as_ref());
+    let investigator = Investigator::try_new(param)?;
+    let mut gate = KindGate::new(&investigator, kind, spec_version.as_deref());
+    if let Kind::SDTM = kind {
+        // spec annotated from an outdated acrf needs another review
+        if let Some(reason) = sdtm_study(param, config_file)?.crf.reason() {
+            gate.spec.push(reason.into());
+        }
+    }
+    let results = results
+        .iter()
+        .map(|result| (result.item.as_str(), result))
+        .collect::<HashMap<_, _>>();
+    let items = config
+        .iter()
+        .map(|item| {
+            let result = results.get(item.name.as_str()).copied();
+            item_stage(&investigator, kind, item, result, &gate)
+        })
+        .collect::<Vec<_>>();
+    let first = Stage::stages(kind)[0];
+    Ok(StageReport {
+        stage: items.iter().map(|item| item.stage).min().unwrap_or(first),
+        spec_version,
+        items,
+        transitions: transitions(kind),
+    })
+}
+
+impl KindGate {
+    fn new(investigator: &Investigator, kind: &Kind, version: Option<&str>) -> KindGate {
+        let mut acrf = vec![];
+        if let Kind::SDTM = kind {
+            match investigator.acrf() {
+                Some(file) => match investigator.sign_off(kind, "acrf") {
+                    Some(sign_off) if sign_off.modified_at.lt(&file.modified_at) => {
+                        acrf.push(format!("{} changed after review sign-off", file.name))
+                    }
+                    Some(_) => {}
+                    None => acrf.push(format!("{} review not signed off", file.name)),
+                },
+                None => acrf.push(format!(
+                    "aCRF not found in {}",
+                    investigator.crf_dir().display()
+                )),
+            }
+        }
+        let document = match kind {
+            Kind::TFLs => "top",
+            _ => "spec",
+        };
+        let key = match version {
+            Some(version) => format!("{}-{}", document, version),
+            None => document.into(),
+        };
+        let mut spec = vec![];
+        if investigator.sign_off(kind, &key).is_none() {
+            spec.push(format!(
+                "{} review not signed off, expect {} in {}",
+                key,
+                key,
+                investigator.review_dir(kind).display()
+            ));
+        }
+        let mut p21 = vec![];
+        if !matches!(kind, Kind::TFLs) {
+            match investigator.p21_report(kind) {
+                Some(report) => {
+                    if let Some(dataset) = investigator.latest_dataset(kind) {
+                        if report.modified_at.lt(&dataset.modified_at) {
+                            p21.push(format!(
+                                "P21 report {} is older than dataset {}",
+                                report.name, dataset.name
+                            ));
+                        }
+                    }
+                }
+                None => p21.push(format!(
+                    "P21 report not found in {}",
+                    investigator.p21_dir().display()
+                )),
+            }
+        }
+        KindGate { acrf, spec, p21 }
+    }
+}
+
+/// first stage of kind whose gate item has not cleared
+fn item_stage(
+    investigator: &Investigator,
+    kind: &Kind,
+    item: &Config,
+    result: Option<&InspectionResult>,
+    gate: &KindGate,
+) -> ItemStage {
+    for stage in Stage::stages(kind) {
+        let blockers = match stage {
+            Stage::AcrfReview => gate.acrf.clone(),
+            Stage::SpecReview => gate.spec.clone(),
+            Stage::Coding => coding_blockers(item, result),
+            Stage::P21 => p21_blockers(result, gate),
+            Stage::OutputReview => output_review_blockers(investigator, kind, item, result),
+            Stage::Complete => vec![],
+        };
+        if !blockers.is_empty() {
+            return ItemStage {
+                item: item.name.clone(),
+                stage: *stage,
+                next: stage.next(kind),
+                blockers,
+            };
+        }
+    }
+    ItemStage {
+        item: item.name.clone(),
+        stage: Stage::Complete,
+        next: None,
+        blockers: vec![],
+    }
+}
+
+/// checks not passed yet, validation is skipped for items not requiring qc
+fn coding_blockers(item: &Config, result: Option<&InspectionResult>) -> Vec<String> {
+    let Some(result) = result else {
+        return vec![format!("{} is not inspected", item.name)];
+    };
+    let mut blockers = vec![];
+    if !result.production_result.start_coding {
+        blockers.push("Production coding not started".into());
+    }
+    if item.qc && !result.validation_result.start_coding {
+        blockers.push("Validation coding not started".into());
+    }
+    for (name, status) in result.checks() {
+        // freshness of p21 report and titles of output are gates of later stages
+        if name.eq("P21") || name.starts_with("Output") {
+            continue;
+        }
+        if !item.qc && (name.starts_with("QC") || name.starts_with("Validation")) {
+            continue;
+        }
+        if status.ne(&Status::Pass) {
+            blockers.push(format!("{} {}", name, status.label()));
+        }
+    }
+    blockers
+}
+
+/// report of kind must be up to date, and issues rejected for item must be fixed
+fn p21_blockers(result: Option<&InspectionResult>, gate: &KindGate) -> Vec<String> {
+    let mut blockers = gate.p21.clone();
+    if let Some(p21) = result.and_then(|result| result.p21.as_ref()) {
+        if let Some(reason) = p21.status.reason() {
+            blockers.push(reason.into());
+        }
+        if p21.counts.reject > 0 {
+            blockers.push(format!("{} P21 reject issues", p21.counts.reject));
+        }
+    }
+    blockers
+}
+
+/// titles of output must match top before review is signed off, so an output which is
+/// missing or could not be read blocks it too, and lint must be clean if the study settings say so
+fn output_review_blockers(
+    investigator: &Investigator,
+    kind: &Kind,
+    item: &Config,
+    result: Option<&InspectionResult>,
+) -> Vec<String> {
+    let mut blockers = vec![];
+    if let Some(review) = result.and_then(|result| result.output_review.as_ref()) {
+        match &review.status {
+            Status::Missing => blockers.push(format!("Output of {} not found", item.name)),
+            Status::Error(reason) => blockers.push(reason.clone()),
+            _ => {}
+        }
+        for mismatch in review.mismatches.iter() {
+            blockers.push(format!(
+                "{:?} in TOP not found: {}",
+                mismatch.field, mismatch.top
+            ));
+        }
+    }
+    // lint findings are only advisory unless the study asks otherwise
+    if investigator.settings().fail_review_on_lint {
+        if let Some(lint) = result.and_then(|result| result.output_lint.as_ref()) {
+            blockers.extend(lint.findings.iter().map(|finding| finding.message.clone()));
+        }
+    }
+    if investigator.sign_off(kind, &item.name).is_none() {
+        blockers.push(format!("Output review of {} not signed off", item.name));
+    }
+    blockers
+}
+
+/// version in file name of spec or crf, eg. `v0.4` of `AK112-303 SDTM Specification v0.4.xlsx`
+pub(super) fn file_version(file: &Path) -> Option<String> {
+    let name = file.file_stem()?.to_string_lossy();
+    FILE_VERSION
+        .captures_iter(&name)
+        .last()
+        .map(|captures| format!("v{}", &captures[1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::inspect::result::IndividualResult;
+    use rust_xlsxwriter::Workbook;
+    use std::fs;
+
+    #[test]
+    fn test_transitions() {
+        assert_eq!(Stage::AcrfReview.next(&Kind::SDTM), Some(Stage::SpecReview));
+        assert_eq!(Stage::Coding.next(&Kind::TFLs), Some(Stage::OutputReview));
+        assert_eq!(Stage::Complete.next(&Kind::ADaM), None);
+        assert!(Stage::P21.can_transit(&Stage::Coding, &Kind::SDTM));
+        assert!(!Stage::Coding.can_transit(&Stage::OutputReview, &Kind::SDTM));
+        assert!(!Stage::AcrfReview.can_transit(&Stage::SpecReview, &Kind::ADaM));
+        // 3 forward and 6 backward
+        assert_eq!(transitions(&Kind::TFLs).len(), 9);
+        assert_eq!(
+            file_version(Path::new("AK112-303 SDTM Specification v0.4.xlsx")),
+            Some("v0.4".into())
+        );
+        assert_eq!(
+            file_version(Path::new("AK112-303_aCRF_V1.1_20240301.pdf")),
+            Some("v1.1".into())
+        );
+        assert_eq!(file_version(Path::new("top-ak112-303-CSR.xlsx")), None);
+    }
+
+    #[test]
+    fn test_stage() -> anyhow::Result<()> {
+        let root = std::env::temp_dir().join("inspector_stage_test");
+        fs::remove_dir_all(&root).ok();
+        let param = InvestigatorParam {
+            product: "ak112".into(),
+            trial: "303".into(),
+            purpose: "CSR".into(),
+            root: root.clone(),
+        };
+        let investigator = Investigator::new(&param);
+        let review = investigator.review_dir(&Kind::TFLs);
+        fs::create_dir_all(&review)?;
+        let top = root.join("top.xlsx");
+        let mut workbook = Workbook::new();
+        let sheet = workbook.add_worksheet().set_name("top")?;
+        sheet.write_string(0, 0, "Validation Level")?;
+        sheet.write_string(0, 4, "Output Name")?;
+        sheet.write_number(1, 0, 3)?;
+        sheet.write_string(1, 4, "t-14-01-01")?;
+        workbook.save(&top)?;
+
+        let passed = || IndividualResult {
+            start_coding: true,
+            log: Status::Pass,
+            sequence: Status::Pass,
+        };
+        let results = vec![InspectionResult {
+            item: "t-14-01-01".into(),
+            qc: Status::Pass,
+            production_result: passed(),
+            validation_result: passed(),
+            ..Default::default()
+        }];
+        let report = stage(&param, top.clone(), &Kind::TFLs, &results)?;
+        assert_eq!(report.stage, Stage::SpecReview);
+        assert!(report.items[0].blockers[0].starts_with("top review not signed off"));
+
+        fs::write(review.join("top.msg"), "")?;
+        let report = stage(&param, top.clone(), &Kind::TFLs, &results)?;
+        assert_eq!(report.items[0].stage, Stage::OutputReview);
+        assert_eq!(report.items[0].next, Some(Stage::Complete));
+
+        fs::write(review.join("T-14-01-01.pdf"), "")?;
+        let report = stage(&param, top.clone(), &Kind::TFLs, &results)?;
+        assert_eq!(report.stage, Stage::Complete);
+        assert!(report.items[0].blockers.is_empty());
+
+        let unreviewed = |status: Status| {
+            vec![InspectionResult {
+                output_review: Some(status.into()),
+                ..Default::default()
+            }]
+        };
+        let item = Config {
+            name: "t-14-01-01".into(),
+            ..Default::default()
+        };
+        let results = unreviewed(Status::Missing);
+        let blockers = output_review_blockers(&investigator, &Kind::TFLs, &item, results.first());
+        assert_eq!(blockers, vec!["Output of t-14-01-01 not found"]);
+        let results = unreviewed(Status::Error("Failed to read t-14-01-01.rtf".into()));
+        let blockers = output_review_blockers(&investigator, &Kind::TFLs, &item, results.first());
+        assert_eq!(blockers, vec!["Failed to read t-14-01-01.rtf"]);
+
+        let report = stage(&param, top, &Kind::TFLs, &[])?;
+        assert_eq!(report.items[0].stage, Stage::Coding);
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,322 @@
+use super::{
+    error::Result,
+    inspect::result::Status,
+    investigator::{
+        investigator::{File, Investigator, InvestigatorParam},
+        utils::system_time_to_chrono,
+    },
+    stage::file_version,
+};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, TimeZone};
+use lazy_static::lazy_static;
+use regex::bytes::Regex;
+use serde::Serialize;
+use std::{
+    fs,
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+/// bytes read from the end of pdf, the trailer and updates appended by later revisions
+const PDF_TAIL: u64 = 64 * 1024;
+/// bytes read at the offset of document information dictionary
+const PDF_OBJECT: u64 = 4 * 1024;
+/// size of an entry in cross reference table
+const XREF_ENTRY: u64 = 20;
+
+lazy_static! {
+    /// dates in document information dictionary, eg. `/ModDate (D:20240301101500+08'00')`
+    static ref PDF_DATE: Regex =
+        Regex::new(r"/(CreationDate|ModDate)\s*\(D:(\d{4,14})(Z|[+-]\d{2}'?(?:\d{2})?)?").unwrap();
+    /// reference to document information dictionary in trailer, eg. `/Info 12 0 R`
+    static ref PDF_INFO: Regex = Regex::new(r"/Info\s+(\d+)\s+\d+\s+R").unwrap();
+    static ref PDF_STARTXREF: Regex = Regex::new(r"startxref\s+(\d+)").unwrap();
+    /// first subsection of cross reference table, eg. `xref\n0 12\n`
+    static ref XREF_TABLE: Regex = Regex::new(r"^xref\s+(\d+)\s+(\d+) *(?:\r\n|\r|\n)").unwrap();
+    static ref XREF_SUBSECTION: Regex = Regex::new(r"^(\d+)\s+(\d+) *(?:\r\n|\r|\n)").unwrap();
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StudyDocument {
+    pub name: String,
+    pub path: PathBuf,
+    /// version in file name, eg. `v1.1`
+    pub version: Option<String>,
+    /// creation date in pdf metadata
+    #[serde(rename = "createdAt")]
+    pub created_at: Option<DateTime<Local>>,
+    /// modification date in pdf metadata
+    #[serde(rename = "modifiedAt")]
+    pub modified_at: Option<DateTime<Local>>,
+    #[serde(rename = "fileModifiedAt")]
+    pub file_modified_at: DateTime<Local>,
+}
+
+/// study level documents of sdtm, shared by every item
+#[derive(Debug, Serialize)]
+pub struct StudySummary {
+    pub spec: Option<StudyDocument>,
+    pub acrf: Option<StudyDocument>,
+    #[serde(rename = "blankCrf")]
+    pub blank_crf: Option<StudyDocument>,
+    /// whether spec file is modified after the file of the latest acrf
+    pub crf: Status,
+}
+
+impl StudyDocument {
+    fn new(file: File<PathBuf>) -> StudyDocument {
+        let (created_at, modified_at) = pdf_dates(&file.filepath);
+        StudyDocument {
+            version: file_version(&file.filepath),
+            name: file.name,
+            path: file.filepath,
+            created_at,
+            modified_at,
+            file_modified_at: file.modified_at,
+        }
+    }
+}
+
+/// locate crf of trial and check sdtm spec is newer than the annotated one
+pub fn sdtm_study<P: AsRef<Path>>(
+    param: &InvestigatorParam<P>,
+    spec_file: P,
+) -> Result<StudySummary> {
+    let investigator = Investigator::try_new(param)?;
+    let spec = spec_document(spec_file.as_ref());
+    let acrf = investigator.acrf().map(StudyDocument::new);
+    let blank_crf = investigator.blank_crf().map(StudyDocument::new);
+    // spec is a workbook without pdf metadata, so times of both files are compared
+    let crf = match (&spec, &acrf) {
+        (Some(spec), Some(acrf)) if spec.file_modified_at.lt(&acrf.file_modified_at) => {
+            Status::Failed(format!(
+                "SDTM spec {} is older than aCRF {}",
+                spec.name, acrf.name
+            ))
+        }
+        (Some(_), Some(_)) => Status::Pass,
+        _ => Status::Missing,
+    };
+    Ok(StudySummary {
+        spec,
+        acrf,
+        blank_crf,
+        crf,
+    })
+}
+
+fn spec_document(file: &Path) -> Option<StudyDocument> {
+    let modified = fs::metadata(file).and_then(|meta| meta.modified()).ok()?;
+    Some(StudyDocument {
+        name: file.file_name()?.to_string_lossy().to_string(),
+        path: file.to_path_buf(),
+        version: file_version(file),
+        created_at: None,
+        modified_at: None,
+        file_modified_at: system_time_to_chrono(&modified),
+    })
+}
+
+/// creation and modification date of pdf, only its tail is read as later revisions are
+/// appended to the file and the last dates found win, the information dictionary of a single
+/// revision is looked up through the cross reference table, none if it is kept in
+/// compressed object streams
+fn pdf_dates(file: &Path) -> (Option<DateTime<Local>>, Option<DateTime<Local>>) {
+    let Ok(mut pdf) = fs::File::open(file) else {
+        return (None, None);
+    };
+    let Some(tail) = pdf
+        .metadata()
+        .ok()
+        .and_then(|meta| read_at(&mut pdf, meta.len().saturating_sub(PDF_TAIL), PDF_TAIL))
+    else {
+        return (None, None);
+    };
+    let dates = dates_in(&tail);
+    if dates.0.is_some() || dates.1.is_some() {
+        return dates;
+    }
+    info_object(&mut pdf, &tail)
+        .map(|object| dates_in(&object))
+        .unwrap_or((None, None))
+}
+
+fn dates_in(content: &[u8]) -> (Option<DateTime<Local>>, Option<DateTime<Local>>) {
+    let mut dates = (None, None);
+    for captures in PDF_DATE.captures_iter(content) {
+        let digits = String::from_utf8_lossy(&captures[2]);
+        let offset = captures
+            .get(3)
+            .map(|offset| String::from_utf8_lossy(offset.as_bytes()));
+        let Some(date) = pdf_date(&digits, offset.as_deref()) else {
+            continue;
+        };
+        match &captures[1] {
+            b"CreationDate" => dates.0 = Some(date),
+            _ => dates.1 = Some(date),
+        }
+    }
+    dates
+}
+
+/// document information dictionary referred to by trailer, located with the cross reference table
+fn info_object(pdf: &mut fs::File, tail: &[u8]) -> Option<Vec<u8>> {
+    let number = last_number(&PDF_INFO, tail)?;
+    let xref = last_number(&PDF_STARTXREF, tail)?;
+    let head = read_at(pdf, xref, 64)?;
+    let captures = XREF_TABLE.captures(&head)?;
+    let mut position = xref + captures.get(0)?.end() as u64;
+    let mut subsection = (number_of(&captures[1])?, number_of(&captures[2])?);
+    loop {
+        let (first, count) = subsection;
+        if (first..first + count).contains(&number) {
+            let entry = read_at(pdf, position + (number - first) * XREF_ENTRY, XREF_ENTRY)?;
+            let offset = number_of(entry.get(..10)?)?;
+            return read_at(pdf, offset, PDF_OBJECT);
+        }
+        position += count * XREF_ENTRY;
+        let head = read_at(pdf, position, 64)?;
+        let captures = XREF_SUBSECTION.captures(&head)?;
+        position += captures.get(0)?.end() as u64;
+        subsection = (number_of(&captures[1])?, number_of(&captures[2])?);
+    }
+}
+
+fn last_number(regex: &Regex, content: &[u8]) -> Option<u64> {
+    number_of(&regex.captures_iter(content).last()?[1])
+}
+
+fn number_of(digits: &[u8]) -> Option<u64> {
+    String::from_utf8_lossy(digits).trim().parse().ok()
+}
+
+fn read_at(pdf: &mut fs::File, offset: u64, length: u64) -> Option<Vec<u8>> {
+    pdf.seek(SeekFrom::Start(offset)).ok()?;
+    let mut bytes = vec![];
+    pdf.by_ref().take(length).read_to_end(&mut bytes).ok()?;
+    Some(bytes)
+}
+
+/// parse date string of pdf, `YYYYMMDDHHmmSS` where only year is required, followed
+/// by `Z` or an offset like `+08'00'`, local time is assumed without offset
+fn pdf_date(digits: &str, offset: Option<&str>) -> Option<DateTime<Local>> {
+    let field = |start: usize, default: u32| {
+        digits
+            .get(start..start + 2)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(default)
+    };
+    let year = digits.get(0..4)?.parse().ok()?;
+    let naive = NaiveDate::from_ymd_opt(year, field(4, 1), field(6, 1))?.and_hms_opt(
+        field(8, 0),
+        field(10, 0),
+        field(12, 0),
+    )?;
+    let seconds = match offset {
+        None => return Local.from_local_datetime(&naive).earliest(),
+        Some("Z") => 0,
+        Some(offset) => {
+            let digits = offset
+                .chars()
+                .filter(|c| c.is_ascii_digit())
+                .collect::<String>();
+            let hours = digits.get(0..2)?.parse::<i32>().ok()?;
+            let minutes = digits
+                .get(2..4)
+                .and_then(|value| value.parse::<i32>().ok())
+                .unwrap_or(0);
+            let seconds = hours * 3600 + minutes * 60;
+            match offset.starts_with('-') {
+                true => -seconds,
+                false => seconds,
+            }
+        }
+    };
+    FixedOffset::east_opt(seconds)?
+        .from_local_datetime(&naive)
+        .single()
+        .map(|date| date.with_timezone(&Local))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_sdtm_study() -> anyhow::Result<()> {
+        let created = pdf_date("20240301101500", Some("+08'00'")).unwrap();
+        assert_eq!(
+            created.with_timezone(&Utc),
+            Utc.with_ymd_and_hms(2024, 3, 1, 2, 15, 0).unwrap()
+        );
+        let date = pdf_date("2024", Some("Z")).unwrap();
+        assert_eq!(
+            date.with_timezone(&Utc),
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+        );
+        assert!(pdf_date("20", None).is_none());
+
+        let root = std::env::temp_dir().join("inspector_study_test");
+        fs::remove_dir_all(&root).ok();
+        let param = InvestigatorParam {
+            product: "ak112".into(),
+            trial: "303".into(),
+            purpose: "CSR".into(),
+            root: root.clone(),
+        };
+        let crf = Investigator::new(&param).crf_dir();
+        fs::create_dir_all(&crf)?;
+        let spec = root.join("AK112-303 SDTM Specification v0.4.xlsx");
+        fs::write(&spec, "")?;
+        let summary = sdtm_study(&param, spec.clone())?;
+        assert_eq!(summary.crf, Status::Missing);
+        assert_eq!(summary.spec.as_ref().unwrap().version, Some("v0.4".into()));
+
+        // information dictionary of a single revision is only found through the xref table
+        let object = "1 0 obj\n<< /CreationDate (D:20240301101500+08'00') >>\nendobj\n";
+        let mut blank = format!("%PDF-1.4\n{}%{}\n", object, " ".repeat(PDF_TAIL as usize));
+        let xref = blank.len();
+        blank.push_str(&format!(
+            "xref\n0 2\n0000000000 65535 f \n{:010} 00000 n \ntrailer\n<< /Size 2 /Info 1 0 R >>\nstartxref\n{}\n%%EOF",
+            "%PDF-1.4\n".len(),
+            xref
+        ));
+        fs::write(crf.join("AK112-303_CRF_V1.0.pdf"), blank)?;
+        fs::write(crf.join("AK112-303 CRF Completion Guidelines v1.0.pdf"), "")?;
+        // metadata of the appended revision is far in the future, yet only file times are compared
+        let acrf_file = crf.join("AK112-303 aCRF v1.1.pdf");
+        fs::write(
+            &acrf_file,
+            "%PDF-1.4\n<< /CreationDate (D:20240301101500+08'00') >>\n\
+             << /CreationDate (D:20240301101500+08'00') /ModDate (D:29990101000000Z) >>",
+        )?;
+        let hour_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+        fs::File::options()
+            .write(true)
+            .open(&spec)?
+            .set_modified(hour_ago)?;
+        let summary = sdtm_study(&param, spec.clone())?;
+        let acrf = summary.acrf.unwrap();
+        assert_eq!(acrf.name, "AK112-303 aCRF v1.1.pdf");
+        assert_eq!(acrf.version, Some("v1.1".into()));
+        assert_eq!(acrf.created_at, Some(created));
+        let blank_crf = summary.blank_crf.unwrap();
+        assert_eq!(blank_crf.name, "AK112-303_CRF_V1.0.pdf");
+        assert_eq!(blank_crf.created_at, Some(created));
+        assert_eq!(
+            summary.crf,
+            Status::Failed(
+                "SDTM spec AK112-303 SDTM Specification v0.4.xlsx is older than aCRF AK112-303 aCRF v1.1.pdf"
+                    .into()
+            )
+        );
+        fs::File::options()
+            .write(true)
+            .open(&acrf_file)?
+            .set_modified(hour_ago - std::time::Duration::from_secs(60))?;
+        assert_eq!(sdtm_study(&param, spec.clone())?.crf, Status::Pass);
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,294 @@
+use super::{
+    category::Kind,
+    error::{Cause, Error, Result},
+    inspect::result::{InspectionResult, Status},
+};
+use std::{collections::HashMap, fmt::Write, fs, path::Path};
+
+/// longest reason kept in a summary line, logs may produce very long messages
+const MAX_REASON_LENGTH: usize = 160;
+
+const STATES: [Status; 5] = [
+    Status::Pass,
+    Status::Failed(String::new()),
+    Status::Error(String::new()),
+    Status::Missing,
+    Status::NotStart,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SummaryFormat {
+    Markdown,
+    Text,
+}
+
+pub struct SummarySection {
+    pub kind: Kind,
+    pub results: Vec<InspectionResult>,
+    /// results of a previous run to list changed items against
+    pub previous: Option<Vec<InspectionResult>>,
+}
+
+/// read results saved as json by a previous run
+pub fn load_results<P: AsRef<Path>>(file: P) -> Result<Vec<InspectionResult>> {
+    let path = file.as_ref();
+    let failed = |source: Cause| Error::ReadResultFailed {
+        path: path.to_path_buf(),
+        source,
+    };
+    let content = fs::read_to_string(path).map_err(|e| failed(Box::new(e)))?;
+    serde_json::from_str(&content).map_err(|e| failed(Box::new(e)))
+}
+
+/// render a compact summary of sections, with at most `top` blockers per kind
+pub fn summarize(
+    title: &str,
+    sections: &[SummarySection],
+    top: usize,
+    format: SummaryFormat,
+) -> String {
+    let mut summary = String::new();
+    let markdown = format == SummaryFormat::Markdown;
+    match markdown {
+        true => writeln!(summary, "# {}\n", title).ok(),
+        false => writeln!(summary, "{}\n", title).ok(),
+    };
+    render_counts(&mut summary, sections, markdown);
+    let blockers = sections
+        .iter()
+        .map(|section| (&section.kind, blockers(&section.results, top)))
+        .filter(|(_, blockers)| !blockers.is_empty())
+        .collect::<Vec<_>>();
+    if !blockers.is_empty() {
+        heading(&mut summary, "Blockers", markdown);
+        for (kind, blockers) in blockers {
+            subheading(&mut summary, kind_name(kind), markdown);
+            for (index, (item, reasons)) in blockers.iter().enumerate() {
+                let reasons = reasons.join("; ");
+                match markdown {
+                    true => writeln!(summary, "{}. **{}** {}", index + 1, item, reasons).ok(),
+                    false => writeln!(summary, "  {}. {} - {}", index + 1, item, reasons).ok(),
+                };
+            }
+        }
+    }
+    let changes = sections
+        .iter()
+        .filter_map(|section| {
+            let previous = section.previous.as_ref()?;
+            Some((&section.kind, changes(previous, &section.results)))
+        })
+        .collect::<Vec<_>>();
+    if !changes.is_empty() {
+        heading(&mut summary, "Changes", markdown);
+        for (kind, changes) in changes {
+            subheading(&mut summary, kind_name(kind), markdown);
+            if changes.is_empty() {
+                match markdown {
+                    true => writeln!(summary, "No changes").ok(),
+                    false => writeln!(summary, "  No changes").ok(),
+                };
+            }
+            for (item, change) in changes {
+                match markdown {
+                    true => writeln!(summary, "- **{}** {}", item, change).ok(),
+                    false => writeln!(summary, "  - {}: {}", item, change).ok(),
+                };
+            }
+        }
+    }
+    summary
+}
+
+fn render_counts(summary: &mut String, sections: &[SummarySection], markdown: bool) {
+    if markdown {
+        summary.push_str("| Kind |");
+        for state in STATES.iter() {
+            write!(summary, " {} |", state.label()).ok();
+        }
+        summary.push_str(" Total |\n|---|");
+        summary.push_str(&"---:|".repeat(STATES.len() + 1));
+        summary.push('\n');
+    }
+    for section in sections.iter() {
+        let counts = counts(&section.results);
+        let kind = kind_name(&section.kind);
+        if markdown {
+            write!(summary, "| {} |", kind).ok();
+            for count in counts.iter() {
+                write!(summary, " {} |", count).ok();
+            }
+            writeln!(summary, " {} |", section.results.len()).ok();
+        } else {
+            let counts = STATES
+                .iter()
+                .zip(counts.iter())
+                .map(|(state, count)| format!("{} {}", count, state.label()))
+                .collect::<Vec<_>>();
+            writeln!(
+                summary,
+                "{}: {} ({} items)",
+                kind,
+                counts.join(", "),
+                section.results.len()
+            )
+            .ok();
+        }
+    }
+}
+
+fn heading(summary: &mut String, name: &str, markdown: bool) {
+    match markdown {
+        true => writeln!(summary, "\n## {}", name).ok(),
+        false => writeln!(summary, "\n{}", name.to_uppercase()).ok(),
+    };
+}
+
+fn subheading(summary: &mut String, name: &str, markdown: bool) {
+    match markdown {
+        true => writeln!(summary, "\n### {}\n", name).ok(),
+        false => writeln!(summary, "{}", name).ok(),
+    };
+}
+
+/// number of items per overall status, in the order of `STATES`
+fn counts(results: &[InspectionResult]) -> Vec<usize> {
+    let mut counts = vec![0; STATES.len()];
+    for result in results.iter() {
+        let severity = result.overall().severity();
+        if let Some(index) = STATES.iter().position(|s| s.severity() == severity) {
+            counts[index] += 1;
+        }
+    }
+    counts
+}
+
+/// items with failed or erroneous checks, the ones with most of them first
+fn blockers(results: &[InspectionResult], top: usize) -> Vec<(&str, Vec<String>)> {
+    let mut blockers = results
+        .iter()
+        .map(|result| {
+            let reasons = result
+                .checks()
+                .into_iter()
+                .filter_map(|(name, status)| {
+                    let reason = status.reason()?;
+                    Some(format!("{} {}: {}", name, status.label(), shorten(reason)))
+                })
+                .collect::<Vec<_>>();
+            (result.item.as_str(), reasons)
+        })
+        .filter(|(_, reasons)| !reasons.is_empty())
+        .collect::<Vec<_>>();
+    // stable sort keeps config order for items with the same number of reasons
+    blockers.sort_by_key(|(_, reasons)| std::cmp::Reverse(reasons.len()));
+    blockers.truncate(top);
+    blockers
+}
+
+/// items whose checks differ from the previous results, described by check
+fn changes<'a>(
+    previous: &[InspectionResult],
+    results: &'a [InspectionResult],
+) -> Vec<(&'a str, String)> {
+    let previous = previous
+        .iter()
+        .map(|result| (result.item.as_str(), result))
+        .collect::<HashMap<_, _>>();
+    results
+        .iter()
+        .filter_map(|result| {
+            let Some(before) = previous.get(result.item.as_str()) else {
+                return Some((
+                    result.item.as_str(),
+                    format!("new, {}", result.overall().label()),
+                ));
+            };
+            let before = before.checks().into_iter().collect::<HashMap<_, _>>();
+            let changed = result
+                .checks()
+                .into_iter()
+                .filter_map(|(name, status)| match before.get(name) {
+                    Some(old) if old.label().eq(status.label()) => None,
+                    Some(old) => Some(format!("{} {} -> {}", name, old.label(), status.label())),
+                    None => Some(format!("{} {}", name, status.label())),
+                })
+                .collect::<Vec<_>>();
+            (!changed.is_empty()).then(|| (result.item.as_str(), changed.join(", ")))
+        })
+        .collect()
+}
+
+fn kind_name(kind: &Kind) -> &'static str {
+    match kind {
+        Kind::SDTM => "SDTM",
+        Kind::ADaM => "ADaM",
+        Kind::TFLs => "TFLs",
+    }
+}
+
+/// reason on a single line, cut at `MAX_REASON_LENGTH` characters
+fn shorten(reason: &str) -> String {
+    let reason = reason.split_whitespace().collect::<Vec<_>>().join(" ");
+    match reason.char_indices().nth(MAX_REASON_LENGTH) {
+        Some((index, _)) => format!("{}...", &reason[..index]),
+        None => reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::inspect::result::IndividualResult;
+
+    fn result(item: &str, qc: Status, log: Status) -> InspectionResult {
+        InspectionResult {
+            item: item.into(),
+            qc,
+            production_result: IndividualResult {
+                start_coding: true,
+                log,
+                sequence: Status::Pass,
+            },
+            validation_result: IndividualResult {
+                start_coding: true,
+                log: Status::Pass,
+                sequence: Status::Pass,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let previous = vec![
+            result("ae", Status::Failed("Value differs".into()), Status::Pass),
+            result("dm", Status::Pass, Status::Pass),
+        ];
+        let results = vec![
+            result("ae", Status::Pass, Status::Pass),
+            result(
+                "dm",
+                Status::Failed("Value differs".into()),
+                Status::Failed("ERROR:\n  Variable AGE not found".into()),
+            ),
+            result("lb", Status::Missing, Status::Pass),
+        ];
+        let sections = vec![SummarySection {
+            kind: Kind::SDTM,
+            results,
+            previous: Some(previous),
+        }];
+        let markdown = summarize("ak112 303 CSR", &sections, 5, SummaryFormat::Markdown);
+        assert!(markdown.contains("| SDTM | 1 | 1 | 0 | 1 | 0 | 3 |"));
+        assert!(markdown.contains(
+            "1. **dm** QC Failed: Value differs; Production Log Failed: ERROR: Variable AGE not found"
+        ));
+        assert!(markdown.contains("- **ae** QC Failed -> Pass"));
+        assert!(markdown.contains("- **lb** new, Missing"));
+        let text = summarize("ak112 303 CSR", &sections, 0, SummaryFormat::Text);
+        assert!(text.contains("SDTM: 1 Pass, 1 Failed, 0 Error, 1 Missing, 0 Not Start (3 items)"));
+        assert!(!text.contains("BLOCKERS"));
+        assert!(text.contains("  - dm: QC Pass -> Failed, Production Log Pass -> Failed"));
+    }
+}
@@ -0,0 +1,338 @@
+use super::{
+    category::Kind,
+    config::reader::{content_sheet, read_config, Config},
+    error::{Error, Result},
+    inspect::result::{InspectionResult, Status},
+};
+use calamine::{open_workbook, Data, Range, Reader, Xlsx};
+use rust_xlsxwriter::{
+    Color, ConditionalFormatText, ConditionalFormatTextRule, Format, Workbook, Worksheet,
+};
+use std::{collections::HashMap, fs, io, path::Path};
+
+pub const TRACKER_SHEET: &str = "tracker";
+
+const HEADERS: [&str; 8] = [
+    "Production Start",
+    "Production Log",
+    "Production Sequence",
+    "Validation Start",
+    "Validation Log",
+    "Validation Sequence",
+    "QC",
+    "QC Supp",
+];
+
+/// text a status cell begins with and its background and font color
+const HIGHLIGHTS: [(&str, u32, u32); 6] = [
+    ("Pass", 0xC6EFCE, 0x006100),
+    ("Started", 0xC6EFCE, 0x006100),
+    ("Failed", 0xFFC7CE, 0x9C0006),
+    ("Error", 0xFF9999, 0x9C0006),
+    ("Missing", 0xEDEDED, 0x7F7F7F),
+    ("Not Start", 0xFFEB9C, 0x9C5700),
+];
+
+/// write a tracker workbook with one row per config item in original order
+pub fn export_tracker<P: AsRef<Path>>(
+    config: &[Config],
+    results: &[InspectionResult],
+    file: P,
+) -> Result<()> {
+    let results = by_item(results);
+    let mut items = config.iter().collect::<Vec<_>>();
+    items.sort_by_key(|item| item.order);
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet().set_name(TRACKER_SHEET)?;
+    let header = Format::new().set_bold();
+    sheet.write_string_with_format(0, 0, "Item", &header)?;
+    sheet.set_column_width(0, 20)?;
+    for (index, name) in HEADERS.iter().enumerate() {
+        let col = index as u16 + 1;
+        sheet.write_string_with_format(0, col, *name, &header)?;
+        sheet.set_column_width(col, 22)?;
+    }
+    for (index, item) in items.iter().enumerate() {
+        let row = index as u32 + 1;
+        sheet.write_string(row, 0, &item.name)?;
+        if let Some(result) = results.get(item.name.as_str()) {
+            write_statuses(sheet, row, 1, result)?;
+        }
+    }
+    let last_row = items.len() as u32;
+    sheet.autofilter(0, 0, last_row, HEADERS.len() as u16)?;
+    sheet.set_freeze_panes(1, 1)?;
+    highlight(sheet, 1, 1, last_row, HEADERS.len() as u16)?;
+    workbook.save(file.as_ref())?;
+    Ok(())
+}
+
+/// copy config workbook to output with status columns appended to the sheet
+/// listing items, every other cell of the workbook is kept as it is and the
+/// config file itself is never written
+pub fn annotate_config<P: AsRef<Path>>(
+    config_file: P,
+    kind: &Kind,
+    results: &[InspectionResult],
+    output: P,
+) -> Result<()> {
+    if same_file(config_file.as_ref(), output.as_ref()) {
+        return Err(Error::OutputFailed(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "annotations are not written over the config file",
+        )));
+    }
+    let config = read_config(config_file.as_ref(), kind)?;
+    let results = by_item(results);
+    let content = content_sheet(kind);
+    let mut source: Xlsx<_> = open_workbook(config_file.as_ref())?;
+    let mut workbook = Workbook::new();
+    for name in source.sheet_names() {
+        let range = source.worksheet_range(&name)?;
+        let sheet = workbook.add_worksheet().set_name(&name)?;
+        copy_range(sheet, &range)?;
+        if !name.eq_ignore_ascii_case(content) {
+            continue;
+        }
+        let (start_row, start_col) = range.start().unwrap_or((0, 0));
+        let first_col = start_col + range.width() as u32;
+        let first_col = first_col as u16;
+        let Some(first) = config.iter().map(|item| item.order).min() else {
+            continue;
+        };
+        // header goes to the row above the first item, as the original header does
+        if first > 0 {
+            let header = Format::new().set_bold();
+            let row = start_row + first as u32 - 1;
+            for (index, name) in HEADERS.iter().enumerate() {
+                sheet.write_string_with_format(row, first_col + index as u16, *name, &header)?;
+            }
+        }
+        let mut last_row = start_row;
+        for item in config.iter() {
+            let row = start_row + item.order as u32;
+            if let Some(result) = results.get(item.name.as_str()) {
+                write_statuses(sheet, row, first_col, result)?;
+            }
+            last_row = last_row.max(row);
+        }
+        let last_col = first_col + HEADERS.len() as u16 - 1;
+        highlight(
+            sheet,
+            start_row + first as u32,
+            first_col,
+            last_row,
+            last_col,
+        )?;
+    }
+    workbook.save(output.as_ref())?;
+    Ok(())
+}
+
+/// whether both paths name the same file, an output not yet written is never the config
+fn same_file(config_file: &Path, output: &Path) -> bool {
+    match (fs::canonicalize(config_file), fs::canonicalize(output)) {
+        (Ok(config_file), Ok(output)) => config_file.eq(&output),
+        _ => config_file.eq(output),
+    }
+}
+
+fn by_item(results: &[InspectionResult]) -> HashMap<&str, &InspectionResult> {
+    results
+        .iter()
+        .map(|result| (result.item.as_str(), result))
+        .collect()
+}
+
+fn write_statuses(
+    sheet: &mut Worksheet,
+    row: u32,
+    first_col: u16,
+    result: &InspectionResult,
+) -> Result<()> {
+    let cells = [
+        Some(started(result.production_result.start_coding)),
+        Some(text(&result.production_result.log)),
+        Some(text(&result.production_result.sequence)),
+        Some(started(result.validation_result.start_coding)),
+        Some(text(&result.validation_result.log)),
+        Some(text(&result.validation_result.sequence)),
+        Some(text(&result.qc)),
+        result.qc_supp.as_ref().map(text),
+    ];
+    for (index, cell) in cells.into_iter().enumerate() {
+        if let Some(cell) = cell {
+            sheet.write_string(row, first_col + index as u16, cell)?;
+        }
+    }
+    Ok(())
+}
+
+fn started(start_coding: bool) -> String {
+    match start_coding {
+        true => "Started".into(),
+        false => Status::NotStart.label().into(),
+    }
+}
+
+/// status label followed by the reason of failure
+fn text(status: &Status) -> String {
+    match status.reason() {
+        Some(reason) => format!("{}: {}", status.label(), reason),
+        None => status.label().into(),
+    }
+}
+
+fn highlight(
+    sheet: &mut Worksheet,
+    first_row: u32,
+    first_col: u16,
+    last_row: u32,
+    last_col: u16,
+) -> Result<()> {
+    if last_row < first_row {
+        return Ok(());
+    }
+    for (prefix, background, font) in HIGHLIGHTS {
+        let format = Format::new()
+            .set_background_color(Color::RGB(background))
+            .set_font_color(Color::RGB(font));
+        let rule = ConditionalFormatText::new()
+            .set_rule(ConditionalFormatTextRule::BeginsWith(prefix.into()))
+            .set_format(format);
+        sheet.add_conditional_format(first_row, first_col, last_row, last_col, &rule)?;
+    }
+    Ok(())
+}
+
+/// write cells of range to the same position they are read from
+fn copy_range(sheet: &mut Worksheet, range: &Range<Data>) -> Result<()> {
+    let (start_row, start_col) = match range.start() {
+        Some(start) => start,
+        None => return Ok(()),
+    };
+    let date = Format::new().set_num_format("yyyy-mm-dd");
+    let datetime = Format::new().set_num_format("yyyy-mm-dd hh:mm:ss");
+    for (r, cells) in range.rows().enumerate() {
+        let row = start_row + r as u32;
+        for (c, cell) in cells.iter().enumerate() {
+            let col = (start_col + c as u32) as u16;
+            match cell {
+                Data::Int(value) => sheet.write_number(row, col, *value as f64)?,
+                Data::Float(value) => sheet.write_number(row, col, *value)?,
+                Data::String(value) | Data::DateTimeIso(value) | Data::DurationIso(value) => {
+                    sheet.write_string(row, col, value)?
+                }
+                Data::Bool(value) => sheet.write_boolean(row, col, *value)?,
+                Data::DateTime(value) => {
+                    let value = value.as_f64();
+                    let format = match value.fract() == 0f64 {
+                        true => &date,
+                        false => &datetime,
+                    };
+                    sheet.write_number_with_format(row, col, value, format)?
+                }
+                Data::Error(value) => sheet.write_string(row, col, value.to_string())?,
+                Data::Empty => continue,
+            };
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::inspect::result::IndividualResult;
+    use calamine::DataType;
+    use std::fs;
+
+    fn result(item: &str, qc: Status) -> InspectionResult {
+        InspectionResult {
+            item: item.into(),
+            qc,
+            production_result: IndividualResult {
+                start_coding: true,
+                log: Status::Pass,
+                sequence: Status::Pass,
+            },
+            validation_result: IndividualResult {
+                start_coding: false,
+                log: Status::Missing,
+                sequence: Status::Missing,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_tracker() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join("inspector_tracker_test");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir)?;
+        let top = dir.join("top.xlsx");
+        let mut workbook = Workbook::new();
+        let sheet = workbook.add_worksheet().set_name("top")?;
+        sheet.write_string(0, 0, "Validation Level")?;
+        sheet.write_string(0, 4, "Output Name")?;
+        sheet.write_number(1, 0, 3)?;
+        sheet.write_string(1, 4, "t-14-01-01")?;
+        sheet.write_number(2, 0, 1)?;
+        sheet.write_string(2, 4, "t-14-01-02")?;
+        workbook
+            .add_worksheet()
+            .set_name("notes")?
+            .write_string(2, 1, "draft")?;
+        workbook.save(&top)?;
+
+        let results = vec![
+            result("t-14-01-02", Status::Pass),
+            result("t-14-01-01", Status::Failed("Value differs".into())),
+        ];
+        let tracker = dir.join("tracker.xlsx");
+        let config = read_config(&top, &Kind::TFLs)?;
+        export_tracker(&config, &results, &tracker)?;
+        let mut workbook: Xlsx<_> = open_workbook(&tracker)?;
+        let range = workbook.worksheet_range(TRACKER_SHEET)?;
+        assert_eq!(
+            range.get((1, 0)).and_then(|c| c.as_string()),
+            Some("t-14-01-01".into())
+        );
+        assert_eq!(
+            range.get((1, 7)).and_then(|c| c.as_string()),
+            Some("Failed: Value differs".into())
+        );
+        assert_eq!(
+            range.get((2, 4)).and_then(|c| c.as_string()),
+            Some("Not Start".into())
+        );
+
+        let original = fs::read(&top)?;
+        assert!(annotate_config(&top, &Kind::TFLs, &results, &top).is_err());
+        let annotated = dir.join("top-status.xlsx");
+        annotate_config(&top, &Kind::TFLs, &results, &annotated)?;
+        assert_eq!(fs::read(&top)?, original);
+        let mut workbook: Xlsx<_> = open_workbook(&annotated)?;
+        assert_eq!(workbook.sheet_names(), vec!["top", "notes"]);
+        let range = workbook.worksheet_range("top")?;
+        assert_eq!(
+            range.get((1, 4)).and_then(|c| c.as_string()),
+            Some("t-14-01-01".into())
+        );
+        assert_eq!(
+            range.get((0, 11)).and_then(|c| c.as_string()),
+            Some("QC".into())
+        );
+        assert_eq!(
+            range.get((2, 11)).and_then(|c| c.as_string()),
+            Some("Pass".into())
+        );
+        let range = workbook.worksheet_range("notes")?;
+        assert_eq!(
+            range.get_value((2, 1)).and_then(|c| c.as_string()),
+            Some("draft".into())
+        );
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,252 @@
+use super::{
+    category::Kind,
+    config::reader::{read_config, Config},
+    error::{Error, Result},
+    inspect::{
+        cache::{ResultCache, CACHE_FILE},
+        inspector::{inspector, InspectOption},
+    },
+    investigator::{
+        investigator::{Directory, Investigator, InvestigatorParam},
+        utils::{filename_as, normalize},
+    },
+};
+use notify::{Event, PollWatcher, RecursiveMode, Watcher};
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{self, Sender},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// files are usually written in several steps, wait until no more event comes in
+const SETTLE_INTERVAL: Duration = Duration::from_millis(500);
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy)]
+pub enum WatchMode {
+    /// filesystem notifications, falls back to polling if they are not available
+    Notify,
+    /// scan directories at the interval, for network shares without notifications
+    Poll(Duration),
+}
+
+/// watch directories of a kind, re-inspect the item whenever one of its files changes
+/// and write the updated result to `out` as a json line, runs until the watcher stops
+/// or reports an error
+pub fn watch<P: AsRef<Path>, W: Write>(
+    param: &InvestigatorParam<P>,
+    config_file: P,
+    kind: &Kind,
+    option: &InspectOption,
+    mode: WatchMode,
+    mut out: W,
+) -> Result<()> {
+    let config = read_config(config_file, kind)?;
+    let investigator = Investigator::try_new(param)?;
+    let directories = investigator
+        .directories(kind)
+        .into_iter()
+        .filter(|directory| directory.path.exists())
+        .collect::<Vec<_>>();
+    let cache = Arc::new(match option.cache {
+        true => ResultCache::load(investigator.utility().join(CACHE_FILE)),
+        false => ResultCache::disabled(),
+    });
+    let inspector = inspector(kind, investigator, &config, option, cache.clone());
+    let (tx, rx) = mpsc::channel();
+    let _watcher = watcher(tx, &directories, mode)?;
+    while let Ok(event) = rx.recv() {
+        let mut changed = paths(event)?;
+        while let Ok(event) = rx.recv_timeout(SETTLE_INTERVAL) {
+            changed.extend(paths(event)?);
+        }
+        let investigator = inspector.investigator();
+        for path in changed.iter() {
+            for directory in directories_of(&directories, path) {
+                investigator.refresh(&directory.path);
+            }
+        }
+        let mut items: Vec<&Config> = vec![];
+        for path in changed.iter() {
+            if let Some(item) = item_of(investigator, &directories, &config, kind, path) {
+                if !items.iter().any(|found| found.name.eq(&item.name)) {
+                    items.push(item);
+                }
+            }
+        }
+        for item in items {
+            investigator.forget(&item.name);
+            let result = inspector.inspect_item(item);
+            serde_json::to_writer(&mut out, &result)
+                .map_err(|e| Error::OutputFailed(io::Error::from(e)))?;
+            writeln!(out).map_err(Error::OutputFailed)?;
+            out.flush().map_err(Error::OutputFailed)?;
+        }
+        cache.save().ok();
+    }
+    Ok(())
+}
+
+fn watcher(
+    tx: Sender<notify::Result<Event>>,
+    directories: &[Directory],
+    mode: WatchMode,
+) -> Result<Box<dyn Watcher>> {
+    let interval = match mode {
+        WatchMode::Notify => {
+            let watcher = notify::recommended_watcher(tx.clone()).and_then(|mut watcher| {
+                for directory in directories.iter() {
+                    watcher.watch(&directory.path, RecursiveMode::NonRecursive)?;
+                }
+                Ok(watcher)
+            });
+            if let Ok(watcher) = watcher {
+                return Ok(Box::new(watcher));
+            }
+            DEFAULT_POLL_INTERVAL
+        }
+        WatchMode::Poll(interval) => interval,
+    };
+    let config = notify::Config::default().with_poll_interval(interval);
+    let mut watcher = PollWatcher::new(tx, config)?;
+    for directory in directories.iter() {
+        watcher.watch(&directory.path, RecursiveMode::NonRecursive)?;
+    }
+    Ok(Box::new(watcher))
+}
+
+/// changed files of event, access only events are ignored
+fn paths(event: notify::Result<Event>) -> Result<Vec<PathBuf>> {
+    match event? {
+        event if !event.kind.is_access() => Ok(event.paths),
+        _ => Ok(vec![]),
+    }
+}
+
+/// path with links and relative parts resolved, the path itself if it can not be resolved
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// watched directories holding the changed file, notify may report paths canonicalized or not
+fn directories_of<'a>(directories: &'a [Directory], path: &Path) -> Vec<&'a Directory> {
+    let Some(dir) = path.parent().map(canonical) else {
+        return vec![];
+    };
+    directories
+        .iter()
+        .filter(|directory| canonical(&directory.path).eq(&dir))
+        .collect()
+}
+
+/// config item a changed file belongs to, matched in the same way as files are located
+fn item_of<'a>(
+    investigator: &Investigator,
+    directories: &[Directory],
+    config: &'a [Config],
+    kind: &Kind,
+    path: &Path,
+) -> Option<&'a Config> {
+    let name = normalize(&path.file_name()?.to_string_lossy());
+    directories_of(directories, path)
+        .into_iter()
+        .find_map(|directory| {
+            config.iter().find(|item| {
+                names(investigator, item, kind).iter().any(|candidate| {
+                    directory.file_types.iter().any(|file_type| {
+                        investigator
+                            .settings()
+                            .formats(file_type)
+                            .iter()
+                            .any(|format| {
+                                normalize(&filename_as(
+                                    candidate,
+                                    &directory.group,
+                                    file_type,
+                                    format,
+                                ))
+                                .eq(&name)
+                            })
+                    })
+                })
+            })
+        })
+}
+
+/// names files of item could be saved as, including supp domain and aliases
+fn names(investigator: &Investigator, item: &Config, kind: &Kind) -> Vec<String> {
+    let mut names = vec![item.name.clone()];
+    if let Kind::SDTM = kind {
+        names.push(format!("supp{}", item.name));
+    }
+    let aliases = names
+        .iter()
+        .filter_map(|name| investigator.settings().alias(name))
+        .map(|alias| alias.to_string())
+        .collect::<Vec<_>>();
+    names.extend(aliases);
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::category::{FileType, Group};
+
+    #[test]
+    fn test_item_of() {
+        let kind = Kind::SDTM;
+        let investigator = Investigator::new(&InvestigatorParam {
+            product: "ak112".into(),
+            trial: "303".into(),
+            purpose: "CSR".into(),
+            root: Path::new(r"D:\Studies"),
+        });
+        let directories = investigator.directories(&kind);
+        let config = vec![
+            Config {
+                name: "AE".into(),
+                ..Default::default()
+            },
+            Config {
+                name: "DM".into(),
+                ..Default::default()
+            },
+        ];
+        let program = &directories[0].path;
+        let item = |path: PathBuf| {
+            item_of(&investigator, &directories, &config, &kind, &path).map(|c| c.name.clone())
+        };
+        assert_eq!(item(program.join("dm.sas")), Some("DM".into()));
+        assert_eq!(item(program.join("AE.log")), Some("AE".into()));
+        assert_eq!(item(program.join("lb.sas")), None);
+        assert_eq!(item(program.join("dm.rtf")), None);
+    }
+
+    #[test]
+    fn test_directories_of() {
+        let dir = std::env::temp_dir().join("inspector_watch_test");
+        fs::create_dir_all(dir.join("program")).unwrap();
+        fs::create_dir_all(dir.join("output")).unwrap();
+        let directory = |path: PathBuf| Directory {
+            path,
+            group: Group::Production,
+            file_types: vec![FileType::Code],
+        };
+        let directories = vec![
+            directory(dir.join("output").join("..").join("program")),
+            directory(dir.join("output")),
+        ];
+        let canonical_program = fs::canonicalize(dir.join("program")).unwrap();
+        let found = directories_of(&directories, &canonical_program.join("dm.sas"));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, directories[0].path);
+        assert!(directories_of(&directories, &dir.join("dm.sas")).is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,134 @@
+use super::config::variable::VariableType;
+use std::{
+    fs,
+    io::{self, BufReader, Read},
+    path::Path,
+};
+
+const RECORD_SIZE: usize = 80;
+const HEADER_PREFIX: &str = "HEADER RECORD*******";
+const MEMBER_HEADERS: [&str; 2] = ["MEMBER  HEADER RECORD", "MEMBV8  HEADER RECORD"];
+const NAMESTR_HEADERS: [&str; 2] = ["NAMESTR HEADER RECORD", "NAMSTV8 HEADER RECORD"];
+const OBS_HEADERS: [&str; 2] = ["OBS     HEADER RECORD", "OBSV8   HEADER RECORD"];
+const DEFAULT_NAMESTR_SIZE: usize = 140;
+// the member header record holds the size of each namestr at bytes 74..78, while namestr header holds the variable count at bytes 54..58
+const NAMESTR_SIZE_RANGE: (usize, usize) = (74, 78);
+const VARIABLE_COUNT_RANGE: (usize, usize) = (54, 58);
+const NUMERIC_TYPE: i16 = 1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatasetVariable {
+    pub name: String,
+    pub label: String,
+    pub data_type: VariableType,
+    pub length: usize,
+}
+
+/// read variable definitions from the header of a SAS transport (v5/v8) file,
+/// only the header records are read, observations are skipped
+pub fn read_variables<P: AsRef<Path>>(file: P) -> io::Result<Vec<DatasetVariable>> {
+    let mut reader = BufReader::new(fs::File::open(file)?);
+    let mut record = [0u8; RECORD_SIZE];
+    let mut namestr_size = DEFAULT_NAMESTR_SIZE;
+    loop {
+        reader.read_exact(&mut record)?;
+        if header_matches(&record, &MEMBER_HEADERS) {
+            namestr_size = ascii_number(&record, NAMESTR_SIZE_RANGE).unwrap_or(namestr_size);
+        }
+        if header_matches(&record, &OBS_HEADERS) {
+            return Err(invalid_data("namestr records not found"));
+        }
+        if header_matches(&record, &NAMESTR_HEADERS) {
+            break;
+        }
+    }
+    let count = ascii_number(&record, VARIABLE_COUNT_RANGE)
+        .ok_or_else(|| invalid_data("invalid variable count in namestr header"))?;
+    let mut namestrs = vec![0u8; count * namestr_size];
+    reader.read_exact(&mut namestrs)?;
+    Ok(namestrs
+        .chunks(namestr_size)
+        .map(|namestr| DatasetVariable {
+            name: ascii_string(&namestr[8..16]).to_uppercase(),
+            label: ascii_string(&namestr[16..56]),
+            data_type: match i16::from_be_bytes([namestr[0], namestr[1]]) {
+                NUMERIC_TYPE => VariableType::Num,
+                _ => VariableType::Char,
+            },
+            length: i16::from_be_bytes([namestr[4], namestr[5]]).max(0) as usize,
+        })
+        .collect())
+}
+
+fn header_matches(record: &[u8], headers: &[&str]) -> bool {
+    let record = String::from_utf8_lossy(record);
+    match record.strip_prefix(HEADER_PREFIX) {
+        Some(header) => headers.iter().any(|target| header.starts_with(target)),
+        None => false,
+    }
+}
+
+fn ascii_number(record: &[u8], range: (usize, usize)) -> Option<usize> {
+    ascii_string(record.get(range.0..range.1)?).parse().ok()
+}
+
+fn ascii_string(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .trim_end_matches(['\0', ' '])
+        .trim()
+        .to_string()
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(content: &str) -> Vec<u8> {
+        format!("{:<80}", content).into_bytes()
+    }
+
+    fn namestr(kind: i16, length: i16, name: &str, label: &str) -> Vec<u8> {
+        let mut namestr = vec![0u8; DEFAULT_NAMESTR_SIZE];
+        namestr[0..2].copy_from_slice(&kind.to_be_bytes());
+        namestr[4..6].copy_from_slice(&length.to_be_bytes());
+        namestr[8..16].copy_from_slice(format!("{:<8}", name).as_bytes());
+        namestr[16..56].copy_from_slice(format!("{:<40}", label).as_bytes());
+        namestr
+    }
+
+    #[test]
+    fn test_read_variables() -> anyhow::Result<()> {
+        let mut content = vec![];
+        content.extend(record(
+            "HEADER RECORD*******LIBRARY HEADER RECORD!!!!!!!000000000000000000000000000000",
+        ));
+        content.extend(record(
+            "HEADER RECORD*******MEMBER  HEADER RECORD!!!!!!!000000000000000001600000000140",
+        ));
+        content.extend(record(
+            "HEADER RECORD*******NAMESTR HEADER RECORD!!!!!!!000000000200000000000000000000",
+        ));
+        content.extend(namestr(
+            2,
+            200,
+            "AETERM",
+            "Reported Term for the Adverse Event",
+        ));
+        content.extend(namestr(1, 8, "AESEQ", "Sequence Number"));
+        let file = std::env::temp_dir().join("inspector_xpt_test.xpt");
+        fs::write(&file, content)?;
+        let variables = read_variables(&file)?;
+        assert_eq!(variables.len(), 2);
+        assert_eq!(variables[0].name, "AETERM");
+        assert_eq!(variables[0].length, 200);
+        assert_eq!(variables[0].data_type, VariableType::Char);
+        assert_eq!(variables[1].label, "Sequence Number");
+        assert_eq!(variables[1].data_type, VariableType::Num);
+        fs::remove_file(&file)?;
+        Ok(())
+    }
+}